@@ -0,0 +1,495 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+use crate::api::{
+    schema::{
+        r#type::{
+            character::{Character, Cooldown},
+            fight::FightResult,
+            item::{ItemCode, ItemComponent, ItemSlot},
+        },
+        response::{
+            my_characters::{
+                CharacterBankExpansionData, CharacterCraftData, CharacterEquipData,
+                CharacterFightData, CharacterGatherData, CharacterGoldTransactionData,
+                CharacterItemTransactionData, CharacterMovementData, CharacterRecycleData,
+                CharacterRestData, CharacterUseItemData,
+            },
+            ResponseData, ResponseError,
+        },
+    },
+    Api,
+};
+
+/// A character action that can be sent to the API.
+pub trait Action {
+    type ResponseData: ActionOutcome;
+
+    /// Perform the action for character `name`.
+    fn execute(
+        &self,
+        api: &Api,
+        name: &str,
+    ) -> impl Future<Output = Result<Self::ResponseData, ResponseError>> + Send;
+}
+
+/// Data common to every action response.
+pub trait ActionOutcome {
+    /// Cooldown started by the action.
+    fn cooldown(&self) -> &Cooldown;
+
+    /// Character state once the action is done.
+    fn character(&self) -> &Character;
+
+    /// Short human-readable description of the result, e.g. for logs.
+    fn describe(&self) -> String;
+}
+
+macro_rules! outcome {
+    ($($data:ty => |$this:ident| $description:expr),+ $(,)?) => {
+        $(
+            impl ActionOutcome for $data {
+                fn cooldown(&self) -> &Cooldown {
+                    &self.cooldown
+                }
+
+                fn character(&self) -> &Character {
+                    &self.character
+                }
+
+                fn describe(&self) -> String {
+                    let $this = self;
+                    $description
+                }
+            }
+        )+
+    };
+}
+
+outcome!(
+    CharacterMovementData => |data| format!(
+        "moved to {} ({}, {})",
+        data.destination.name, data.destination.x, data.destination.y
+    ),
+    CharacterRestData => |data| format!("restored {} HP", data.hp_restored),
+    CharacterEquipData => |data| format!(
+        "{} slot: {}",
+        serde_plain::to_string(&data.slot).unwrap_or_default(),
+        data.item.code
+    ),
+    CharacterUseItemData => |data| format!("used {}", data.item.code),
+    CharacterFightData => |data| format!(
+        "{} in {} turns: {} XP, {} gold, dropped {}",
+        match data.fight.result {
+            FightResult::Win => "won",
+            FightResult::Lose => "lost",
+        },
+        data.fight.turns,
+        data.fight.xp,
+        data.fight.gold,
+        list(&data.fight.drops)
+    ),
+    CharacterGatherData => |data| format!(
+        "gathered {}: {} XP",
+        list(&data.details.items),
+        data.details.xp
+    ),
+    CharacterCraftData => |data| format!(
+        "crafted {}: {} XP",
+        list(&data.details.items),
+        data.details.xp
+    ),
+    CharacterGoldTransactionData => |data| format!("{} gold in bank", data.bank.quantity),
+    CharacterItemTransactionData => |data| format!(
+        "moved {} between bank and inventory",
+        data.item.code
+    ),
+    CharacterRecycleData => |data| format!("recycled into {}", list(&data.details.items)),
+    CharacterBankExpansionData => |data| format!(
+        "bought a bank expansion for {} gold",
+        data.transaction.price
+    ),
+);
+
+impl ActionOutcome for ResponseData {
+    fn cooldown(&self) -> &Cooldown {
+        self.outcome().cooldown()
+    }
+
+    fn character(&self) -> &Character {
+        self.outcome().character()
+    }
+
+    fn describe(&self) -> String {
+        self.outcome().describe()
+    }
+}
+
+impl ResponseData {
+    fn outcome(&self) -> &dyn ActionOutcome {
+        match self {
+            ResponseData::CharacterMovement(data) => data,
+            ResponseData::CharacterRest(data) => data,
+            ResponseData::CharacterEquip(data) => data,
+            ResponseData::CharacterUseItem(data) => data,
+            ResponseData::CharacterFight(data) => data,
+            ResponseData::CharacterGather(data) => data,
+            ResponseData::CharacterCraft(data) => data,
+            ResponseData::CharacterGoldTransaction(data) => data,
+            ResponseData::CharacterItemTransaction(data) => data,
+            ResponseData::CharacterRecycle(data) => data,
+            ResponseData::CharacterBankExpansion(data) => data,
+        }
+    }
+}
+
+/// "2 copper_ore, 1 sap", or "nothing".
+fn list(items: &[ItemComponent]) -> String {
+    if items.is_empty() {
+        return "nothing".to_string();
+    }
+    items
+        .iter()
+        .map(|item| format!("{} {}", item.quantity, item.code))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Move to a tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Move {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Move {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Action for Move {
+    type ResponseData = CharacterMovementData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_move(name, self.x, self.y).await
+    }
+}
+
+/// Recover HP.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rest;
+
+impl Action for Rest {
+    type ResponseData = CharacterRestData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_rest(name).await
+    }
+}
+
+/// Equip an item from the inventory.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Equip {
+    pub code: ItemCode,
+    pub slot: ItemSlot,
+    pub quantity: u32,
+}
+
+impl Equip {
+    pub fn new(code: ItemCode, slot: ItemSlot) -> Self {
+        Self {
+            code,
+            slot,
+            quantity: 1,
+        }
+    }
+}
+
+impl Action for Equip {
+    type ResponseData = CharacterEquipData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_equip_item(name, &self.code, &self.slot, self.quantity)
+            .await
+    }
+}
+
+/// Move an equipped item back to the inventory.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unequip {
+    pub slot: ItemSlot,
+    pub quantity: u32,
+}
+
+impl Unequip {
+    pub fn new(slot: ItemSlot) -> Self {
+        Self { slot, quantity: 1 }
+    }
+}
+
+impl Action for Unequip {
+    type ResponseData = CharacterEquipData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_unequip_item(name, &self.slot, self.quantity)
+            .await
+    }
+}
+
+/// Consume an item, e.g. food.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UseItem {
+    pub code: ItemCode,
+    pub quantity: u32,
+}
+
+impl UseItem {
+    pub fn new(code: ItemCode, quantity: u32) -> Self {
+        Self { code, quantity }
+    }
+}
+
+impl Action for UseItem {
+    type ResponseData = CharacterUseItemData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_use_item(name, &self.code, self.quantity).await
+    }
+}
+
+/// Fight the monster on the current tile.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fight;
+
+impl Action for Fight {
+    type ResponseData = CharacterFightData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_fight(name).await
+    }
+}
+
+/// Gather the resource on the current tile.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gather;
+
+impl Action for Gather {
+    type ResponseData = CharacterGatherData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_gather(name).await
+    }
+}
+
+/// Craft an item in the workshop of the current tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Craft {
+    pub code: ItemCode,
+    pub quantity: u32,
+}
+
+impl Craft {
+    pub fn new(code: ItemCode, quantity: u32) -> Self {
+        Self { code, quantity }
+    }
+}
+
+impl Action for Craft {
+    type ResponseData = CharacterCraftData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_craft(name, &self.code, self.quantity).await
+    }
+}
+
+/// Recycle an item in the workshop of the current tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recycle {
+    pub code: ItemCode,
+    pub quantity: u32,
+}
+
+impl Recycle {
+    pub fn new(code: ItemCode, quantity: u32) -> Self {
+        Self { code, quantity }
+    }
+}
+
+impl Action for Recycle {
+    type ResponseData = CharacterRecycleData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_recycle(name, &self.code, self.quantity).await
+    }
+}
+
+/// Deposit gold in the bank of the current tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositGold {
+    pub quantity: u32,
+}
+
+impl DepositGold {
+    pub fn new(quantity: u32) -> Self {
+        Self { quantity }
+    }
+}
+
+impl Action for DepositGold {
+    type ResponseData = CharacterGoldTransactionData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_deposit_gold(name, self.quantity).await
+    }
+}
+
+/// Withdraw gold from the bank of the current tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WithdrawGold {
+    pub quantity: u32,
+}
+
+impl WithdrawGold {
+    pub fn new(quantity: u32) -> Self {
+        Self { quantity }
+    }
+}
+
+impl Action for WithdrawGold {
+    type ResponseData = CharacterGoldTransactionData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_withdraw_gold(name, self.quantity).await
+    }
+}
+
+/// Deposit items in the bank of the current tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub code: ItemCode,
+    pub quantity: u32,
+}
+
+impl Deposit {
+    pub fn new(code: ItemCode, quantity: u32) -> Self {
+        Self { code, quantity }
+    }
+}
+
+impl Action for Deposit {
+    type ResponseData = CharacterItemTransactionData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_deposit_item(name, &self.code, self.quantity)
+            .await
+    }
+}
+
+/// Withdraw items from the bank of the current tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Withdraw {
+    pub code: ItemCode,
+    pub quantity: u32,
+}
+
+impl Withdraw {
+    pub fn new(code: ItemCode, quantity: u32) -> Self {
+        Self { code, quantity }
+    }
+}
+
+impl Action for Withdraw {
+    type ResponseData = CharacterItemTransactionData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_withdraw_item(name, &self.code, self.quantity)
+            .await
+    }
+}
+
+/// Buy more slots for the bank, with the gold of the character.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuyBankExpansion;
+
+impl Action for BuyBankExpansion {
+    type ResponseData = CharacterBankExpansionData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        api.action_buy_bank_expansion(name).await
+    }
+}
+
+/// Any action, for storing heterogeneous action queues.
+///
+/// Serialized with an `action` tag, e.g. `{"action": "move", "x": 1, "y": 2}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum CharacterAction {
+    Move(Move),
+    Rest(Rest),
+    Equip(Equip),
+    Unequip(Unequip),
+    UseItem(UseItem),
+    Fight(Fight),
+    Gather(Gather),
+    Craft(Craft),
+    Recycle(Recycle),
+    DepositGold(DepositGold),
+    WithdrawGold(WithdrawGold),
+    Deposit(Deposit),
+    Withdraw(Withdraw),
+    BuyBankExpansion(BuyBankExpansion),
+}
+
+macro_rules! character_action {
+    ($($action:ident),+ $(,)?) => {
+        $(
+            impl From<$action> for CharacterAction {
+                fn from(action: $action) -> Self {
+                    CharacterAction::$action(action)
+                }
+            }
+        )+
+    };
+}
+
+character_action!(
+    Move,
+    Rest,
+    Equip,
+    Unequip,
+    UseItem,
+    Fight,
+    Gather,
+    Craft,
+    Recycle,
+    DepositGold,
+    WithdrawGold,
+    Deposit,
+    Withdraw,
+    BuyBankExpansion,
+);
+
+impl Action for CharacterAction {
+    type ResponseData = ResponseData;
+
+    async fn execute(&self, api: &Api, name: &str) -> Result<Self::ResponseData, ResponseError> {
+        Ok(match self {
+            CharacterAction::Move(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Rest(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Equip(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Unequip(action) => action.execute(api, name).await?.into(),
+            CharacterAction::UseItem(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Fight(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Gather(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Craft(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Recycle(action) => action.execute(api, name).await?.into(),
+            CharacterAction::DepositGold(action) => action.execute(api, name).await?.into(),
+            CharacterAction::WithdrawGold(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Deposit(action) => action.execute(api, name).await?.into(),
+            CharacterAction::Withdraw(action) => action.execute(api, name).await?.into(),
+            CharacterAction::BuyBankExpansion(action) => action.execute(api, name).await?.into(),
+        })
+    }
+}
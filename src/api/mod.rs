@@ -4,16 +4,17 @@ use reqwest::{Client, Method};
 use schema::{
     r#type::{
         character::Character,
+        code::{MapCode, MonsterCode, ResourceCode},
         item::{Item, ItemCode, ItemComponent, ItemSlot, Resource},
-        map::Map,
+        map::{Map, MapContentType},
         monster::Monster,
     },
     response::{
         my_account::AccountBankDetailsData,
         my_characters::{
-            CharacterCraftData, CharacterEquipData, CharacterFightData, CharacterGatherData,
-            CharacterGoldTransactionData, CharacterItemTransactionData, CharacterMovementData,
-            CharacterRecycleData, CharacterRestData, CharacterUseItemData,
+            CharacterBankExpansionData, CharacterCraftData, CharacterEquipData, CharacterFightData,
+            CharacterGatherData, CharacterGoldTransactionData, CharacterItemTransactionData,
+            CharacterMovementData, CharacterRecycleData, CharacterRestData, CharacterUseItemData,
         },
         ResponseError, StatusData,
     },
@@ -31,7 +32,7 @@ pub struct HttpRequest<'a> {
 }
 
 pub trait ApiRequest {
-    fn to_request(&self, name: &str) -> HttpRequest;
+    fn to_request(&self, name: &str) -> HttpRequest<'_>;
 }
 
 pub struct Api {
@@ -196,6 +197,16 @@ impl Api {
         .await
     }
 
+    pub async fn my_characters(&self) -> Result<Vec<Character>, ResponseError> {
+        self.send(HttpRequest {
+            path: "/my/characters".to_string(),
+            data: None,
+            query: None,
+            method: Method::GET,
+        })
+        .await
+    }
+
     /* My characters */
     pub async fn action_move(
         &self,
@@ -225,7 +236,7 @@ impl Api {
     pub async fn action_equip_item(
         &self,
         name: &str,
-        code: &String,
+        code: &ItemCode,
         slot: &ItemSlot,
         quantity: u32,
     ) -> Result<CharacterEquipData, ResponseError> {
@@ -376,6 +387,19 @@ impl Api {
         .await
     }
 
+    pub async fn action_buy_bank_expansion(
+        &self,
+        name: &str,
+    ) -> Result<CharacterBankExpansionData, ResponseError> {
+        self.send(HttpRequest {
+            path: format!("/my/{name}/action/bank/buy_expansion"),
+            data: None,
+            query: None,
+            method: Method::POST,
+        })
+        .await
+    }
+
     /* My account */
     pub async fn bank_items(&self) -> Result<Vec<ItemComponent>, ResponseError> {
         self.send(HttpRequest {
@@ -410,7 +434,7 @@ impl Api {
 
     pub async fn item(&self, code: &ItemCode) -> Result<Item, ResponseError> {
         self.send(HttpRequest {
-            path: format!("/maps/{code}"),
+            path: format!("/items/{code}"),
             data: None,
             query: None,
             method: Method::GET,
@@ -439,7 +463,7 @@ impl Api {
         .await
     }
 
-    pub async fn resource(&self, code: &ItemCode) -> Result<Resource, ResponseError> {
+    pub async fn resource(&self, code: &ResourceCode) -> Result<Resource, ResponseError> {
         self.send(HttpRequest {
             path: format!("/resources/{code}"),
             data: None,
@@ -462,15 +486,16 @@ impl Api {
 
     pub async fn maps_content(
         &self,
-        content_code: &String,
-        content_type: &String,
+        content_code: &MapCode,
+        content_type: MapContentType,
     ) -> Result<Vec<Map>, ResponseError> {
+        let content_type = content_type.to_string();
         self.send(HttpRequest {
             path: "/maps".to_string(),
             data: None,
             query: Some(vec![
                 ("content_code", content_code),
-                ("content_type", content_type),
+                ("content_type", &content_type),
             ]),
             method: Method::GET,
         })
@@ -488,7 +513,7 @@ impl Api {
     }
 
     /* Monsters */
-    pub async fn monster(&self, monster: &str) -> Result<Monster, ResponseError> {
+    pub async fn monster(&self, monster: &MonsterCode) -> Result<Monster, ResponseError> {
         self.send(HttpRequest {
             path: format!("/monsters/{monster}"),
             data: None,
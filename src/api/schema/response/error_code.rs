@@ -0,0 +1,30 @@
+/// The requested item, map or monster does not exist.
+pub const NOT_FOUND: u16 = 404;
+/// The request is invalid, e.g. an item equipped in a slot of another type.
+pub const INVALID_PAYLOAD: u16 = 422;
+/// The bank does not hold enough gold.
+pub const BANK_INSUFFICIENT_GOLD: u16 = 460;
+/// The bank has no free slot.
+pub const BANK_FULL: u16 = 462;
+/// The character or the bank misses an item, or holds too few of it.
+pub const MISSING_ITEM: u16 = 478;
+/// The equipment slot is already used.
+pub const SLOT_NOT_EMPTY: u16 = 485;
+/// The character is already at the destination.
+pub const ALREADY_AT_DESTINATION: u16 = 490;
+/// The equipment slot is empty.
+pub const SLOT_EMPTY: u16 = 491;
+/// The character does not hold enough gold.
+pub const INSUFFICIENT_GOLD: u16 = 492;
+/// The skill level of the character is too low.
+pub const SKILL_LEVEL_TOO_LOW: u16 = 493;
+/// The combat level of the character is too low.
+pub const LEVEL_TOO_LOW: u16 = 496;
+/// The inventory of the character is full.
+pub const INVENTORY_FULL: u16 = 497;
+/// The character does not exist.
+pub const CHARACTER_NOT_FOUND: u16 = 498;
+/// The character is still in cooldown.
+pub const COOLDOWN: u16 = 499;
+/// The tile does not have the content the action needs.
+pub const CONTENT_NOT_FOUND: u16 = 598;
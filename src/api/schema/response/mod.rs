@@ -1,17 +1,67 @@
+/// Codes of the errors returned by the API, in [`Error::code`].
+pub mod error_code;
 pub mod my_account;
 pub mod my_characters;
 
-use my_characters::CharacterMovementData;
+use my_characters::{
+    CharacterBankExpansionData, CharacterCraftData, CharacterEquipData, CharacterFightData,
+    CharacterGatherData, CharacterGoldTransactionData, CharacterItemTransactionData,
+    CharacterMovementData, CharacterRecycleData, CharacterRestData, CharacterUseItemData,
+};
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-use super::Error;
+#[cfg(feature = "chrono")]
+use super::r#type::time::ServerClock;
+use super::{r#type::time::Timestamp, Error};
 
+/// The response of any character action.
+///
+/// Serialized as `{"type": "character_fight", "data": {...}}` so that a log of
+/// responses can be replayed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum ResponseData {
     CharacterMovement(CharacterMovementData),
+    CharacterRest(CharacterRestData),
+    CharacterEquip(CharacterEquipData),
+    CharacterUseItem(CharacterUseItemData),
+    CharacterFight(CharacterFightData),
+    CharacterGather(CharacterGatherData),
+    CharacterCraft(CharacterCraftData),
+    CharacterGoldTransaction(CharacterGoldTransactionData),
+    CharacterItemTransaction(CharacterItemTransactionData),
+    CharacterRecycle(CharacterRecycleData),
+    CharacterBankExpansion(CharacterBankExpansionData),
+}
+
+macro_rules! response_data {
+    ($($variant:ident($data:ty)),+ $(,)?) => {
+        $(
+            impl From<$data> for ResponseData {
+                fn from(data: $data) -> Self {
+                    ResponseData::$variant(data)
+                }
+            }
+        )+
+    };
 }
 
+response_data!(
+    CharacterMovement(CharacterMovementData),
+    CharacterRest(CharacterRestData),
+    CharacterEquip(CharacterEquipData),
+    CharacterUseItem(CharacterUseItemData),
+    CharacterFight(CharacterFightData),
+    CharacterGather(CharacterGatherData),
+    CharacterCraft(CharacterCraftData),
+    CharacterGoldTransaction(CharacterGoldTransactionData),
+    CharacterItemTransaction(CharacterItemTransactionData),
+    CharacterRecycle(CharacterRecycleData),
+    CharacterBankExpansion(CharacterBankExpansionData),
+);
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResponseError {
     pub error: Error,
@@ -43,13 +93,23 @@ pub struct StatusData {
     /// Number of characters currently online.
     pub characters_online: i32,
     /// Current server time in ISO 8601 format.
-    pub server_time: String,
+    pub server_time: Timestamp,
     /// List of server announcements.
     pub announcements: Vec<AnnouncementSchema>,
     /// Datetime of the last server wipe in ISO 8601 format.
-    pub last_wipe: String,
+    pub last_wipe: Timestamp,
     /// Datetime of the next planned server wipe in ISO 8601 format.
-    pub next_wipe: String,
+    pub next_wipe: Timestamp,
+}
+
+#[cfg(feature = "chrono")]
+impl StatusData {
+    /// Estimate the server clock from this status, assuming it was received
+    /// at `local_now`. `None` when the server time cannot be read.
+    pub fn clock(&self, local_now: chrono::DateTime<chrono::Utc>) -> Option<ServerClock> {
+        let server_time = self.server_time.to_datetime()?;
+        Some(ServerClock::new(server_time, local_now))
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -57,5 +117,5 @@ pub struct AnnouncementSchema {
     /// The announcement message text.
     pub message: String,
     /// Datetime when the announcement was created (optional).
-    pub created_at: Option<String>,
+    pub created_at: Option<Timestamp>,
 }
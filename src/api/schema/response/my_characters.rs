@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::api::schema::r#type::{
-    bank::BankGold,
+    bank::{BankExpansion, BankGold},
     character::{Character, Cooldown},
     fight::Fight,
     item::{Item, ItemComponent, ItemDetails, ItemSlot, Recycle},
@@ -102,6 +102,16 @@ pub struct CharacterItemTransactionData {
     pub character: Character,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CharacterBankExpansionData {
+    /// Cooldown details.
+    pub cooldown: Cooldown,
+    /// Transaction details.
+    pub transaction: BankExpansion,
+    /// Character details.
+    pub character: Character,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CharacterRecycleData {
     /// Cooldown details.
@@ -5,3 +5,9 @@ pub struct BankGold {
     /// The quantity of the item.
     pub quantity: u32,
 }
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BankExpansion {
+    /// Price paid for the expansion.
+    pub price: u32,
+}
@@ -1,8 +1,18 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{IgnoredAny, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{collections::BTreeMap, fmt};
 
 use crate::api::ItemSlot;
 
-use super::item::ItemType;
+use super::{
+    code::{InvalidCode, TaskCode},
+    elemental::{self, ElementalStats},
+    item::{ItemCode, ItemType},
+    time::Timestamp,
+};
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Character {
@@ -26,12 +36,9 @@ pub struct Character {
     /// Elemental attributes.
     #[serde(flatten)]
     pub elemental_attributes: ElementalAttributes,
-    /// Equipment slots.
+    /// Equipment and utility slots.
     #[serde(flatten)]
     pub equipment: Equipment,
-    /// Utility slots.
-    #[serde(flatten)]
-    pub utilities: Utilities,
     /// Task details.
     #[serde(flatten)]
     pub task: Task,
@@ -43,12 +50,45 @@ pub struct Character {
     pub cooldown_info: CooldownInfo,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+impl Character {
+    /// Get the progress of a skill. [`Skill::Fighting`] is read from the
+    /// combat level.
+    pub fn skill(&self, skill: Skill) -> SkillProgress {
+        match skill {
+            Skill::Fighting => SkillProgress {
+                level: self.combat_stats.level.max(0) as u32,
+                xp: self.combat_stats.xp.max(0) as u32,
+                max_xp: self.combat_stats.max_xp.max(0) as u32,
+            },
+            _ => self.skills.get(&skill).unwrap_or_default(),
+        }
+    }
+
+    /// Iterate over the progress of every skill, including fighting.
+    pub fn skills(&self) -> impl Iterator<Item = (Skill, SkillProgress)> + '_ {
+        Skill::ALL
+            .into_iter()
+            .map(|skill| (skill, self.skill(skill)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
 }
 
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Number of tiles between two positions, moving horizontally and vertically.
+    pub fn distance(&self, other: &Position) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct CombatStats {
     /// Combat level.
@@ -71,152 +111,208 @@ pub struct CombatStats {
     pub stamina: i32,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct Skills {
-    // Mining
-    #[serde(rename = "mining_level")]
-    pub mining_level: Option<u32>,
-    #[serde(rename = "mining_xp")]
-    pub mining_xp: Option<u32>,
-    #[serde(rename = "mining_max_xp")]
-    pub mining_max_xp: Option<u32>,
-
-    // Woodcutting
-    #[serde(rename = "woodcutting_level")]
-    pub woodcutting_level: Option<u32>,
-    #[serde(rename = "woodcutting_xp")]
-    pub woodcutting_xp: Option<u32>,
-    #[serde(rename = "woodcutting_max_xp")]
-    pub woodcutting_max_xp: Option<u32>,
-
-    // Fishing
-    #[serde(rename = "fishing_level")]
-    pub fishing_level: Option<u32>,
-    #[serde(rename = "fishing_xp")]
-    pub fishing_xp: Option<u32>,
-    #[serde(rename = "fishing_max_xp")]
-    pub fishing_max_xp: Option<u32>,
-
-    // Weaponcrafting
-    #[serde(rename = "weaponcrafting_level")]
-    pub weaponcrafting_level: Option<u32>,
-    #[serde(rename = "weaponcrafting_xp")]
-    pub weaponcrafting_xp: Option<u32>,
-    #[serde(rename = "weaponcrafting_max_xp")]
-    pub weaponcrafting_max_xp: Option<u32>,
-
-    // Gearcrafting
-    #[serde(rename = "gearcrafting_level")]
-    pub gearcrafting_level: Option<u32>,
-    #[serde(rename = "gearcrafting_xp")]
-    pub gearcrafting_xp: Option<u32>,
-    #[serde(rename = "gearcrafting_max_xp")]
-    pub gearcrafting_max_xp: Option<u32>,
-
-    // Jewelrycrafting
-    #[serde(rename = "jewelrycrafting_level")]
-    pub jewelrycrafting_level: Option<u32>,
-    #[serde(rename = "jewelrycrafting_xp")]
-    pub jewelrycrafting_xp: Option<u32>,
-    #[serde(rename = "jewelrycrafting_max_xp")]
-    pub jewelrycrafting_max_xp: Option<u32>,
-
-    // Cooking
-    #[serde(rename = "cooking_level")]
-    pub cooking_level: Option<u32>,
-    #[serde(rename = "cooking_xp")]
-    pub cooking_xp: Option<u32>,
-    #[serde(rename = "cooking_max_xp")]
-    pub cooking_max_xp: Option<u32>,
-
-    // Alchemy
-    #[serde(rename = "alchemy_level")]
-    pub alchemy_level: Option<u32>,
-    #[serde(rename = "alchemy_xp")]
-    pub alchemy_xp: Option<u32>,
-    #[serde(rename = "alchemy_max_xp")]
-    pub alchemy_max_xp: Option<u32>,
+/// Progress of a character in a single [`Skill`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillProgress {
+    /// Current level.
+    pub level: u32,
+    /// XP accumulated in the current level.
+    pub xp: u32,
+    /// XP required to reach the next level.
+    pub max_xp: u32,
+}
+
+/// Non-combat skills of a character, keyed by [`Skill`].
+///
+/// On the wire each skill is three flat fields: `<skill>_level`, `<skill>_xp`
+/// and `<skill>_max_xp`, so adding a [`Skill`] variant is enough to support a
+/// new skill.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Skills(BTreeMap<Skill, SkillProgress>);
+
+impl Skills {
+    /// Get the progress of a skill, if the character reported it.
+    pub fn get(&self, skill: &Skill) -> Option<SkillProgress> {
+        self.0.get(skill).copied()
+    }
+
+    /// Set the progress of a skill.
+    pub fn set(&mut self, skill: Skill, progress: SkillProgress) {
+        self.0.insert(skill, progress);
+    }
+
+    /// Iterate over the reported skills.
+    pub fn iter(&self) -> impl Iterator<Item = (Skill, SkillProgress)> + '_ {
+        self.0.iter().map(|(skill, progress)| (*skill, *progress))
+    }
+}
+
+impl Serialize for Skills {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len() * 3))?;
+        for (skill, progress) in &self.0 {
+            map.serialize_entry(&format!("{skill}_level"), &progress.level)?;
+            map.serialize_entry(&format!("{skill}_xp"), &progress.xp)?;
+            map.serialize_entry(&format!("{skill}_max_xp"), &progress.max_xp)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Skills {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SkillsVisitor;
+
+        impl<'de> Visitor<'de> for SkillsVisitor {
+            type Value = Skills;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("flat `<skill>_level`, `<skill>_xp` and `<skill>_max_xp` fields")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Skills, A::Error> {
+                let mut skills = Skills::default();
+                while let Some(key) = access.next_key::<String>()? {
+                    // `_max_xp` must be tried before `_xp`.
+                    let field = ["_max_xp", "_xp", "_level"].into_iter().find_map(|suffix| {
+                        let skill = key.strip_suffix(suffix)?.parse::<Skill>().ok()?;
+                        Some((skill, suffix))
+                    });
+                    let Some((skill, suffix)) = field else {
+                        access.next_value::<IgnoredAny>()?;
+                        continue;
+                    };
+                    let value = access.next_value::<u32>()?;
+                    let progress = skills.0.entry(skill).or_default();
+                    match suffix {
+                        "_max_xp" => progress.max_xp = value,
+                        "_xp" => progress.xp = value,
+                        _ => progress.level = value,
+                    }
+                }
+                Ok(skills)
+            }
+        }
+
+        deserializer.deserialize_map(SkillsVisitor)
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ElementalAttributes {
     /// Elemental attacks.
-    pub attack_fire: i32,
-    pub attack_earth: i32,
-    pub attack_water: i32,
-    pub attack_air: i32,
+    #[serde(flatten, with = "elemental::attack")]
+    pub attack: ElementalStats<i32>,
     /// Elemental damage bonuses.
-    pub dmg_fire: i32,
-    pub dmg_earth: i32,
-    pub dmg_water: i32,
-    pub dmg_air: i32,
+    #[serde(flatten, with = "elemental::dmg")]
+    pub dmg: ElementalStats<i32>,
     /// Elemental resistances.
-    pub res_fire: i32,
-    pub res_earth: i32,
-    pub res_water: i32,
-    pub res_air: i32,
+    #[serde(flatten, with = "elemental::res")]
+    pub res: ElementalStats<i32>,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// Items currently worn by a character, indexed by [`ItemSlot`].
+///
+/// On the wire this is a flat list of `*_slot` fields where an empty string
+/// means nothing is equipped, plus a quantity for each utility slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "EquipmentSlots", into = "EquipmentSlots")]
 pub struct Equipment {
-    /// Equipment slots.
-    pub weapon_slot: String,
-    pub shield_slot: String,
-    pub helmet_slot: String,
-    pub body_armor_slot: String,
-    pub leg_armor_slot: String,
-    pub boots_slot: String,
-    pub ring1_slot: String,
-    pub ring2_slot: String,
-    pub amulet_slot: String,
-    pub artifact1_slot: String,
-    pub artifact2_slot: String,
-    pub artifact3_slot: String,
+    slots: BTreeMap<ItemSlot, Option<ItemCode>>,
+    utility1_quantity: u32,
+    utility2_quantity: u32,
+}
+
+impl Default for Equipment {
+    fn default() -> Self {
+        Self {
+            slots: ItemSlot::ALL.iter().map(|slot| (*slot, None)).collect(),
+            utility1_quantity: 0,
+            utility2_quantity: 0,
+        }
+    }
 }
 
 impl Equipment {
     /// Check if a slot is empty
     pub fn is_slot_empty(&self, slot: &ItemSlot) -> bool {
-        match slot {
-            ItemSlot::Weapon => self.weapon_slot.is_empty(),
-            ItemSlot::Shield => self.shield_slot.is_empty(),
-            ItemSlot::Helmet => self.helmet_slot.is_empty(),
-            ItemSlot::BodyArmor => self.body_armor_slot.is_empty(),
-            ItemSlot::LegArmor => self.leg_armor_slot.is_empty(),
-            ItemSlot::Boots => self.boots_slot.is_empty(),
-            ItemSlot::Ring1 => self.ring1_slot.is_empty(),
-            ItemSlot::Ring2 => self.ring2_slot.is_empty(),
-            ItemSlot::Amulet => self.amulet_slot.is_empty(),
-            ItemSlot::Artifact1 => self.artifact1_slot.is_empty(),
-            ItemSlot::Artifact2 => self.artifact2_slot.is_empty(),
-            ItemSlot::Artifact3 => self.artifact3_slot.is_empty(),
-            _ => false,
-        }
+        matches!(self.slots.get(slot), Some(None))
     }
 
     /// Get the item code in a specific slot
     pub fn item_in_slot(&self, slot: &ItemSlot) -> Option<&str> {
-        let item = match slot {
-            ItemSlot::Weapon => &self.weapon_slot,
-            ItemSlot::Shield => &self.shield_slot,
-            ItemSlot::Helmet => &self.helmet_slot,
-            ItemSlot::BodyArmor => &self.body_armor_slot,
-            ItemSlot::LegArmor => &self.leg_armor_slot,
-            ItemSlot::Boots => &self.boots_slot,
-            ItemSlot::Ring1 => &self.ring1_slot,
-            ItemSlot::Ring2 => &self.ring2_slot,
-            ItemSlot::Amulet => &self.amulet_slot,
-            ItemSlot::Artifact1 => &self.artifact1_slot,
-            ItemSlot::Artifact2 => &self.artifact2_slot,
-            ItemSlot::Artifact3 => &self.artifact3_slot,
-            _ => return None,
-        };
+        self.get(slot).map(ItemCode::as_str)
+    }
+
+    /// Get the item code in a specific slot
+    pub fn get(&self, slot: &ItemSlot) -> Option<&ItemCode> {
+        self.slots.get(slot).and_then(Option::as_ref)
+    }
+
+    /// Number of items held in a slot: the stack size for utility slots, 1 for
+    /// any other occupied slot and 0 when the slot is empty.
+    pub fn quantity(&self, slot: &ItemSlot) -> u32 {
+        if self.get(slot).is_none() {
+            return 0;
+        }
+        match slot {
+            ItemSlot::Utility1 => self.utility1_quantity,
+            ItemSlot::Utility2 => self.utility2_quantity,
+            _ => 1,
+        }
+    }
+
+    /// Put an item in a slot, returning the previous item if any.
+    ///
+    /// `quantity` is only meaningful for utility slots and is ignored otherwise.
+    pub fn set(&mut self, slot: &ItemSlot, code: ItemCode, quantity: u32) -> Option<ItemCode> {
+        let entry = self.slots.get_mut(slot)?;
+        match slot {
+            ItemSlot::Utility1 => self.utility1_quantity = quantity,
+            ItemSlot::Utility2 => self.utility2_quantity = quantity,
+            _ => {}
+        }
+        entry.replace(code)
+    }
 
-        if item.is_empty() {
-            None
-        } else {
-            Some(item)
+    /// Empty a slot, returning the item it held if any.
+    pub fn clear(&mut self, slot: &ItemSlot) -> Option<ItemCode> {
+        match slot {
+            ItemSlot::Utility1 => self.utility1_quantity = 0,
+            ItemSlot::Utility2 => self.utility2_quantity = 0,
+            _ => {}
         }
+        self.slots.get_mut(slot)?.take()
+    }
+
+    /// Iterate over every slot, occupied or not, in [`ItemSlot::ALL`] order.
+    pub fn slots(&self) -> impl Iterator<Item = (ItemSlot, Option<&ItemCode>)> {
+        self.slots.iter().map(|(slot, code)| (*slot, code.as_ref()))
+    }
+
+    /// Iterate over occupied slots only.
+    pub fn iter(&self) -> impl Iterator<Item = (ItemSlot, &ItemCode)> {
+        self.slots
+            .iter()
+            .filter_map(|(slot, code)| code.as_ref().map(|code| (*slot, code)))
+    }
+
+    /// List the slots whose item or quantity differs between `self` and
+    /// `other`.
+    pub fn diff(&self, other: &Equipment) -> Vec<EquipmentChange> {
+        ItemSlot::ALL
+            .iter()
+            .filter(|slot| {
+                self.get(slot) != other.get(slot) || self.quantity(slot) != other.quantity(slot)
+            })
+            .map(|slot| EquipmentChange {
+                slot: *slot,
+                before: self.get(slot).cloned(),
+                before_quantity: self.quantity(slot),
+                after: other.get(slot).cloned(),
+                after_quantity: other.quantity(slot),
+            })
+            .collect()
     }
 
     /// Find first available slot for an item type
@@ -228,24 +324,146 @@ impl Equipment {
     }
 }
 
+/// A single slot change between two [`Equipment`] snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EquipmentChange {
+    /// The slot that changed.
+    pub slot: ItemSlot,
+    /// Item in the slot before the change.
+    pub before: Option<ItemCode>,
+    /// Quantity in the slot before the change, 0 when it was empty.
+    pub before_quantity: u32,
+    /// Item in the slot after the change.
+    pub after: Option<ItemCode>,
+    /// Quantity in the slot after the change, 0 when it is empty.
+    pub after_quantity: u32,
+}
+
+/// Flat wire representation of [`Equipment`].
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct Utilities {
-    /// Utility slots.
-    pub utility1_slot: String,
-    pub utility1_slot_quantity: i32,
-    pub utility2_slot: String,
-    pub utility2_slot_quantity: i32,
+struct EquipmentSlots {
+    weapon_slot: String,
+    shield_slot: String,
+    helmet_slot: String,
+    body_armor_slot: String,
+    leg_armor_slot: String,
+    boots_slot: String,
+    ring1_slot: String,
+    ring2_slot: String,
+    amulet_slot: String,
+    artifact1_slot: String,
+    artifact2_slot: String,
+    artifact3_slot: String,
+    utility1_slot: String,
+    utility1_slot_quantity: u32,
+    utility2_slot: String,
+    utility2_slot_quantity: u32,
+}
+
+impl TryFrom<EquipmentSlots> for Equipment {
+    type Error = InvalidCode;
+
+    fn try_from(raw: EquipmentSlots) -> Result<Self, Self::Error> {
+        let codes = [
+            raw.weapon_slot,
+            raw.shield_slot,
+            raw.helmet_slot,
+            raw.body_armor_slot,
+            raw.leg_armor_slot,
+            raw.boots_slot,
+            raw.ring1_slot,
+            raw.ring2_slot,
+            raw.amulet_slot,
+            raw.artifact1_slot,
+            raw.artifact2_slot,
+            raw.artifact3_slot,
+            raw.utility1_slot,
+            raw.utility2_slot,
+        ];
+        let slots = ItemSlot::ALL
+            .into_iter()
+            .zip(codes)
+            .map(|(slot, code)| {
+                let code = if code.is_empty() {
+                    None
+                } else {
+                    Some(ItemCode::new(code)?)
+                };
+                Ok((slot, code))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            slots,
+            utility1_quantity: raw.utility1_slot_quantity,
+            utility2_quantity: raw.utility2_slot_quantity,
+        })
+    }
+}
+
+impl From<Equipment> for EquipmentSlots {
+    fn from(equipment: Equipment) -> Self {
+        let code = |slot: ItemSlot| {
+            equipment
+                .get(&slot)
+                .map(ItemCode::to_string)
+                .unwrap_or_default()
+        };
+        Self {
+            weapon_slot: code(ItemSlot::Weapon),
+            shield_slot: code(ItemSlot::Shield),
+            helmet_slot: code(ItemSlot::Helmet),
+            body_armor_slot: code(ItemSlot::BodyArmor),
+            leg_armor_slot: code(ItemSlot::LegArmor),
+            boots_slot: code(ItemSlot::Boots),
+            ring1_slot: code(ItemSlot::Ring1),
+            ring2_slot: code(ItemSlot::Ring2),
+            amulet_slot: code(ItemSlot::Amulet),
+            artifact1_slot: code(ItemSlot::Artifact1),
+            artifact2_slot: code(ItemSlot::Artifact2),
+            artifact3_slot: code(ItemSlot::Artifact3),
+            utility1_slot: code(ItemSlot::Utility1),
+            utility1_slot_quantity: equipment.quantity(&ItemSlot::Utility1),
+            utility2_slot: code(ItemSlot::Utility2),
+            utility2_slot_quantity: equipment.quantity(&ItemSlot::Utility2),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Task {
-    /// Task details.
-    pub task: String,
+    /// Task details. The API sends an empty code when there is no task.
+    #[serde(with = "task_code")]
+    pub task: Option<TaskCode>,
     pub task_type: String,
     pub task_progress: i32,
     pub task_total: i32,
 }
 
+/// A task code sent as a string, empty when there is no task.
+mod task_code {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::TaskCode;
+
+    pub fn serialize<S: Serializer>(
+        code: &Option<TaskCode>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(code.as_ref().map_or("", TaskCode::as_str))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<TaskCode>, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        if code.is_empty() {
+            return Ok(None);
+        }
+        TaskCode::new(code).map(Some).map_err(D::Error::custom)
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct InventoryInfo {
     /// Inventory details.
@@ -253,11 +471,69 @@ pub struct InventoryInfo {
     pub inventory: Option<Vec<InventorySlot>>,
 }
 
+impl InventoryInfo {
+    /// Iterate over the occupied slots as item codes and quantities.
+    pub fn items(&self) -> impl Iterator<Item = (ItemCode, u32)> + '_ {
+        self.inventory
+            .iter()
+            .flatten()
+            .filter(|slot| slot.quantity > 0)
+            .filter_map(|slot| Some((slot.code.parse().ok()?, slot.quantity)))
+    }
+
+    /// Quantity of an item held in the inventory.
+    pub fn quantity(&self, code: &str) -> u32 {
+        self.inventory
+            .iter()
+            .flatten()
+            .filter(|slot| slot.code == code)
+            .map(|slot| slot.quantity)
+            .sum()
+    }
+
+    /// Total quantity of items held, over every slot.
+    pub fn total_items(&self) -> u32 {
+        self.inventory
+            .iter()
+            .flatten()
+            .map(|slot| slot.quantity)
+            .sum()
+    }
+
+    /// Items that can still be added before reaching `inventory_max_items`.
+    pub fn free_capacity(&self) -> u32 {
+        self.inventory_max_items.saturating_sub(self.total_items())
+    }
+
+    /// Number of slots holding no item.
+    pub fn free_slots(&self) -> usize {
+        self.inventory
+            .iter()
+            .flatten()
+            .filter(|slot| slot.quantity == 0 || slot.code.is_empty())
+            .count()
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct CooldownInfo {
     /// Cooldown details.
     pub cooldown: i32,
-    pub cooldown_expiration: Option<String>,
+    pub cooldown_expiration: Option<Timestamp>,
+}
+
+#[cfg(feature = "chrono")]
+impl CooldownInfo {
+    /// Time left before the character can act again, measured at `now`.
+    ///
+    /// A missing or unreadable expiration counts as no cooldown.
+    pub fn remaining(&self, now: chrono::DateTime<chrono::Utc>) -> std::time::Duration {
+        self.cooldown_expiration
+            .as_ref()
+            .and_then(Timestamp::to_datetime)
+            .and_then(|expiration| (expiration - now).to_std().ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -289,13 +565,28 @@ pub struct Cooldown {
     /// The remaining seconds of the cooldown.
     pub remaining_seconds: i32,
     /// The start of the cooldown.
-    pub started_at: String,
+    pub started_at: Timestamp,
     /// The expiration of the cooldown.
-    pub expiration: String,
+    pub expiration: Timestamp,
     /// The reason of the cooldown.
     pub reason: CooldownReason,
 }
 
+#[cfg(feature = "chrono")]
+impl Cooldown {
+    /// Time left before the cooldown expires, measured at `now`.
+    ///
+    /// Use [`ServerClock::now`](super::time::ServerClock::now) rather than the
+    /// local time to account for clock drift. An unreadable expiration counts
+    /// as no cooldown.
+    pub fn remaining(&self, now: chrono::DateTime<chrono::Utc>) -> std::time::Duration {
+        self.expiration
+            .to_datetime()
+            .and_then(|expiration| (expiration - now).to_std().ok())
+            .unwrap_or_default()
+    }
+}
+
 /// The reason for the cooldown.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -330,35 +621,39 @@ pub enum CooldownReason {
     BuyBankExpansion,
 }
 
-/// Represents the different skills that a character has within the game.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "lowercase")]
-pub enum Skill {
-    /// Fishing skill, allowing for gathering fish.
-    Fishing,
-    /// Cooking skill, allowing the preparation of food.
-    Cooking,
-    /// Gear crafting skill, for creating and enhancing gear.
-    Gearcrafting,
-    /// Mining skill, for gathering ores and minerals.
-    Mining,
-    /// Jewelry crafting skill, for making rings and amulets.
-    Jewelrycrafting,
-    /// Weapon crafting skill, for creating various weapons.
-    Weaponcrafting,
-    /// Woodcutting skill, for gathering wood and related resources.
-    Woodcutting,
-    /// Alchemy skill, for creating potion and related resources.
-    Alchemy,
-    /// Fighting skill
-    Fighting,
+with_all! {
+    /// Represents the different skills that a character has within the game.
+    #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[serde(rename_all = "lowercase")]
+    pub enum Skill {
+        /// Fishing skill, allowing for gathering fish.
+        Fishing,
+        /// Cooking skill, allowing the preparation of food.
+        Cooking,
+        /// Gear crafting skill, for creating and enhancing gear.
+        Gearcrafting,
+        /// Mining skill, for gathering ores and minerals.
+        Mining,
+        /// Jewelry crafting skill, for making rings and amulets.
+        Jewelrycrafting,
+        /// Weapon crafting skill, for creating various weapons.
+        Weaponcrafting,
+        /// Woodcutting skill, for gathering wood and related resources.
+        Woodcutting,
+        /// Alchemy skill, for creating potion and related resources.
+        Alchemy,
+        /// Fighting skill
+        Fighting,
+    }
 }
 
-impl Skill {
-    pub fn to_string(&self) -> String {
-        serde_plain::to_string(self).unwrap()
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_plain::to_string(self).unwrap())
     }
+}
 
+impl Skill {
     pub fn is_gathering(&self) -> bool {
         matches!(
             self,
@@ -371,17 +666,6 @@ impl std::str::FromStr for Skill {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "fishing" => Ok(Skill::Fishing),
-            "cooking" => Ok(Skill::Cooking),
-            "gearcrafting" => Ok(Skill::Gearcrafting),
-            "mining" => Ok(Skill::Mining),
-            "jewelrycrafting" => Ok(Skill::Jewelrycrafting),
-            "weaponcrafting" => Ok(Skill::Weaponcrafting),
-            "woodcutting" => Ok(Skill::Woodcutting),
-            "alchemy" => Ok(Skill::Alchemy),
-            "fighting" => Ok(Skill::Fighting),
-            _ => Err(format!("Unknown skill: {}", s)),
-        }
+        serde_plain::from_str(&s.to_lowercase()).map_err(|_| format!("Unknown skill: {}", s))
     }
 }
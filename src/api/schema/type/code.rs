@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::{borrow::Borrow, fmt, ops::Deref, str::FromStr};
+use thiserror::Error;
+
+/// Error returned when a string does not match the `^[a-zA-Z0-9_-]+$` code pattern.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("invalid {kind} code `{code}`")]
+pub struct InvalidCode {
+    /// Kind of code that was expected, e.g. `item`.
+    pub kind: &'static str,
+    /// The rejected input.
+    pub code: String,
+}
+
+fn is_valid_code(code: &str) -> bool {
+    !code.is_empty()
+        && code
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+macro_rules! code {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Validate and wrap a code.
+            pub fn new(code: impl Into<String>) -> Result<Self, InvalidCode> {
+                let code = code.into();
+                if is_valid_code(&code) {
+                    Ok(Self(code))
+                } else {
+                    Err(InvalidCode { kind: $kind, code })
+                }
+            }
+
+            /// The code as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Unwrap the underlying string.
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = InvalidCode;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::new(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = InvalidCode;
+
+            fn try_from(code: String) -> Result<Self, Self::Error> {
+                Self::new(code)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = InvalidCode;
+
+            fn try_from(code: &str) -> Result<Self, Self::Error> {
+                Self::new(code)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(code: $name) -> Self {
+                code.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+code!(
+    /// Unique identifier of an item.
+    ItemCode,
+    "item"
+);
+code!(
+    /// Unique identifier of a monster.
+    MonsterCode,
+    "monster"
+);
+code!(
+    /// Unique identifier of a resource.
+    ResourceCode,
+    "resource"
+);
+code!(
+    /// Unique identifier of an NPC.
+    NpcCode,
+    "npc"
+);
+code!(
+    /// Unique identifier of a task.
+    TaskCode,
+    "task"
+);
+code!(
+    /// Code of the content of a map tile. Depending on the content type this
+    /// is a monster, a resource, a workshop skill or a building name.
+    MapCode,
+    "map content"
+);
+
+impl From<MonsterCode> for MapCode {
+    fn from(code: MonsterCode) -> Self {
+        Self(code.0)
+    }
+}
+
+impl From<ResourceCode> for MapCode {
+    fn from(code: ResourceCode) -> Self {
+        Self(code.0)
+    }
+}
+
+impl From<NpcCode> for MapCode {
+    fn from(code: NpcCode) -> Self {
+        Self(code.0)
+    }
+}
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::{
+    character::{Equipment, Skill},
+    elemental::ElementalStats,
+    fight::Element,
+    item::{Item, ItemCode, ItemEffect},
+};
+
+/// A typed item effect.
+///
+/// On the wire an effect is a `{ "name", "value" }` pair; names that are not
+/// recognised are kept as [`Effect::Unknown`] so new game effects do not break
+/// deserialization.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "ItemEffect", into = "ItemEffect")]
+pub enum Effect {
+    /// Elemental attack.
+    Attack(Element, i32),
+    /// Elemental damage bonus, in percent.
+    Dmg(Element, i32),
+    /// Elemental resistance, in percent.
+    Res(Element, i32),
+    /// Additional max HP.
+    Hp(i32),
+    /// Haste, reduces fight cooldown.
+    Haste(i32),
+    /// Critical strike chance, in percent.
+    CriticalStrike(i32),
+    /// Additional XP, in percent.
+    Wisdom(i32),
+    /// Additional drop chance.
+    Prospecting(i32),
+    /// HP healed when the item is used outside of a fight.
+    Heal(i32),
+    /// HP restored during a fight.
+    Restore(i32),
+    /// Temporary max HP boost during a fight.
+    BoostHp(i32),
+    /// Temporary elemental damage boost during a fight, in percent.
+    BoostDmg(Element, i32),
+    /// Temporary elemental resistance boost during a fight, in percent.
+    BoostRes(Element, i32),
+    /// Additional inventory space.
+    InventorySpace(i32),
+    /// Gathering cooldown reduction for a skill, in percent (usually negative).
+    Gathering(Skill, i32),
+    /// An effect this crate does not know about yet.
+    Unknown { name: String, value: i32 },
+}
+
+impl Effect {
+    /// Wire name of the effect, e.g. `attack_fire`.
+    pub fn name(&self) -> String {
+        match self {
+            Effect::Attack(element, _) => format!("attack_{element}"),
+            Effect::Dmg(element, _) => format!("dmg_{element}"),
+            Effect::Res(element, _) => format!("res_{element}"),
+            Effect::Hp(_) => "hp".to_string(),
+            Effect::Haste(_) => "haste".to_string(),
+            Effect::CriticalStrike(_) => "critical_strike".to_string(),
+            Effect::Wisdom(_) => "wisdom".to_string(),
+            Effect::Prospecting(_) => "prospecting".to_string(),
+            Effect::Heal(_) => "heal".to_string(),
+            Effect::Restore(_) => "restore".to_string(),
+            Effect::BoostHp(_) => "boost_hp".to_string(),
+            Effect::BoostDmg(element, _) => format!("boost_dmg_{element}"),
+            Effect::BoostRes(element, _) => format!("boost_res_{element}"),
+            Effect::InventorySpace(_) => "inventory_space".to_string(),
+            Effect::Gathering(skill, _) => skill.to_string(),
+            Effect::Unknown { name, .. } => name.clone(),
+        }
+    }
+
+    /// Numeric value of the effect.
+    pub fn value(&self) -> i32 {
+        match self {
+            Effect::Attack(_, value)
+            | Effect::Dmg(_, value)
+            | Effect::Res(_, value)
+            | Effect::Hp(value)
+            | Effect::Haste(value)
+            | Effect::CriticalStrike(value)
+            | Effect::Wisdom(value)
+            | Effect::Prospecting(value)
+            | Effect::Heal(value)
+            | Effect::Restore(value)
+            | Effect::BoostHp(value)
+            | Effect::BoostDmg(_, value)
+            | Effect::BoostRes(_, value)
+            | Effect::InventorySpace(value)
+            | Effect::Gathering(_, value)
+            | Effect::Unknown { value, .. } => *value,
+        }
+    }
+}
+
+impl From<ItemEffect> for Effect {
+    fn from(effect: ItemEffect) -> Self {
+        let ItemEffect { name, value } = effect;
+        let element = |prefix: &str| name.strip_prefix(prefix)?.parse::<Element>().ok();
+
+        if let Some(element) = element("boost_dmg_") {
+            return Effect::BoostDmg(element, value);
+        }
+        if let Some(element) = element("boost_res_") {
+            return Effect::BoostRes(element, value);
+        }
+        if let Some(element) = element("attack_") {
+            return Effect::Attack(element, value);
+        }
+        if let Some(element) = element("dmg_") {
+            return Effect::Dmg(element, value);
+        }
+        if let Some(element) = element("res_") {
+            return Effect::Res(element, value);
+        }
+
+        match name.as_str() {
+            "hp" => Effect::Hp(value),
+            "haste" => Effect::Haste(value),
+            "critical_strike" => Effect::CriticalStrike(value),
+            "wisdom" => Effect::Wisdom(value),
+            "prospecting" => Effect::Prospecting(value),
+            "heal" => Effect::Heal(value),
+            "restore" => Effect::Restore(value),
+            "boost_hp" => Effect::BoostHp(value),
+            "inventory_space" => Effect::InventorySpace(value),
+            _ => match name.parse::<Skill>() {
+                Ok(skill) if skill.is_gathering() => Effect::Gathering(skill, value),
+                _ => Effect::Unknown { name, value },
+            },
+        }
+    }
+}
+
+impl From<Effect> for ItemEffect {
+    fn from(effect: Effect) -> Self {
+        Self {
+            name: effect.name(),
+            value: effect.value(),
+        }
+    }
+}
+
+/// Sum of the effects of a set of items.
+///
+/// Fight boosts from utilities are kept apart from the stats they boost, and
+/// `heal` apart from `restore`, as they apply at different times.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatBlock {
+    pub attack: ElementalStats<i32>,
+    pub dmg: ElementalStats<i32>,
+    pub res: ElementalStats<i32>,
+    pub hp: i32,
+    pub haste: i32,
+    pub critical_strike: i32,
+    pub wisdom: i32,
+    pub prospecting: i32,
+    /// HP healed when used outside of a fight.
+    pub heal: i32,
+    /// HP restored during a fight.
+    pub restore: i32,
+    pub boost_hp: i32,
+    pub boost_dmg: ElementalStats<i32>,
+    pub boost_res: ElementalStats<i32>,
+    pub inventory_space: i32,
+    pub gathering: BTreeMap<Skill, i32>,
+}
+
+impl StatBlock {
+    /// Add a single effect to the block.
+    pub fn add(&mut self, effect: &Effect) {
+        match effect {
+            Effect::Attack(element, value) => self.attack[*element] += value,
+            Effect::Dmg(element, value) => self.dmg[*element] += value,
+            Effect::Res(element, value) => self.res[*element] += value,
+            Effect::Hp(value) => self.hp += value,
+            Effect::Haste(value) => self.haste += value,
+            Effect::CriticalStrike(value) => self.critical_strike += value,
+            Effect::Wisdom(value) => self.wisdom += value,
+            Effect::Prospecting(value) => self.prospecting += value,
+            Effect::Heal(value) => self.heal += value,
+            Effect::Restore(value) => self.restore += value,
+            Effect::BoostHp(value) => self.boost_hp += value,
+            Effect::BoostDmg(element, value) => self.boost_dmg[*element] += value,
+            Effect::BoostRes(element, value) => self.boost_res[*element] += value,
+            Effect::InventorySpace(value) => self.inventory_space += value,
+            Effect::Gathering(skill, value) => *self.gathering.entry(*skill).or_default() += value,
+            Effect::Unknown { .. } => {}
+        }
+    }
+
+    /// Sum the effects of every item in a loadout.
+    ///
+    /// Items missing from `items` are ignored.
+    pub fn from_equipment(equipment: &Equipment, items: &HashMap<ItemCode, Item>) -> Self {
+        equipment
+            .iter()
+            .filter_map(|(_, code)| items.get(code))
+            .flat_map(|item| item.effects.iter())
+            .collect()
+    }
+}
+
+impl<'a> FromIterator<&'a Effect> for StatBlock {
+    fn from_iter<I: IntoIterator<Item = &'a Effect>>(iter: I) -> Self {
+        let mut block = StatBlock::default();
+        iter.into_iter().for_each(|effect| block.add(effect));
+        block
+    }
+}
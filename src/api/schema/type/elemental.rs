@@ -0,0 +1,264 @@
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Sub, SubAssign},
+};
+
+use super::fight::Element;
+
+/// One value per [`Element`].
+///
+/// Serializes as `{ "fire", "earth", "water", "air" }`. Use the [`attack`],
+/// [`dmg`] and [`res`] modules with `#[serde(flatten, with = "...")]` for the
+/// prefixed flat fields (`attack_fire`, ...) found on characters and monsters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ElementalStats<T> {
+    pub fire: T,
+    pub earth: T,
+    pub water: T,
+    pub air: T,
+}
+
+impl<T> ElementalStats<T> {
+    /// Build stats by computing the value of each element.
+    pub fn from_fn(mut f: impl FnMut(Element) -> T) -> Self {
+        Self {
+            fire: f(Element::Fire),
+            earth: f(Element::Earth),
+            water: f(Element::Water),
+            air: f(Element::Air),
+        }
+    }
+
+    /// Get the value of an element.
+    pub fn get(&self, element: Element) -> &T {
+        match element {
+            Element::Fire => &self.fire,
+            Element::Earth => &self.earth,
+            Element::Water => &self.water,
+            Element::Air => &self.air,
+        }
+    }
+
+    /// Get a mutable reference to the value of an element.
+    pub fn get_mut(&mut self, element: Element) -> &mut T {
+        match element {
+            Element::Fire => &mut self.fire,
+            Element::Earth => &mut self.earth,
+            Element::Water => &mut self.water,
+            Element::Air => &mut self.air,
+        }
+    }
+
+    /// Iterate over every element and its value, in [`Element::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (Element, &T)> {
+        Element::ALL
+            .into_iter()
+            .map(move |element| (element, self.get(element)))
+    }
+
+    /// Apply `f` to every value.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> ElementalStats<U> {
+        ElementalStats::from_fn(|element| f(self.get(element)))
+    }
+
+    /// Combine two stats element by element.
+    pub fn zip_with<U, V>(
+        &self,
+        other: &ElementalStats<U>,
+        mut f: impl FnMut(&T, &U) -> V,
+    ) -> ElementalStats<V> {
+        ElementalStats::from_fn(|element| f(self.get(element), other.get(element)))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Default> ElementalStats<T> {
+    /// Sum of the values of all elements.
+    pub fn total(&self) -> T {
+        self.iter()
+            .fold(T::default(), |total, (_, value)| total + *value)
+    }
+}
+
+impl<T> Index<Element> for ElementalStats<T> {
+    type Output = T;
+
+    fn index(&self, element: Element) -> &T {
+        self.get(element)
+    }
+}
+
+impl<T> IndexMut<Element> for ElementalStats<T> {
+    fn index_mut(&mut self, element: Element) -> &mut T {
+        self.get_mut(element)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Add for ElementalStats<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| *a + *b)
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Sub for ElementalStats<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| *a - *b)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul for ElementalStats<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| *a * *b)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for ElementalStats<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|a| *a * rhs)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for ElementalStats<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for ElementalStats<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+fn serialize_prefixed<T: Serialize, S: Serializer>(
+    stats: &ElementalStats<T>,
+    prefix: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(Element::ALL.len()))?;
+    for (element, value) in stats.iter() {
+        map.serialize_entry(&format!("{prefix}{element}"), value)?;
+    }
+    map.end()
+}
+
+/// Every element is required: a missing field is an error rather than a
+/// silent zero.
+fn deserialize_prefixed<'de, T, D>(
+    prefix: &'static str,
+    deserializer: D,
+) -> Result<ElementalStats<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    struct PrefixedVisitor<T> {
+        prefix: &'static str,
+        marker: PhantomData<T>,
+    }
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for PrefixedVisitor<T> {
+        type Value = ElementalStats<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "flat `{}<element>` fields", self.prefix)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut stats = ElementalStats::<Option<T>>::from_fn(|_| None);
+            while let Some(key) = access.next_key::<String>()? {
+                let element = key
+                    .strip_prefix(self.prefix)
+                    .and_then(|element| element.parse::<Element>().ok());
+                match element {
+                    Some(element) => stats[element] = Some(access.next_value()?),
+                    None => {
+                        access.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            match stats {
+                ElementalStats {
+                    fire: Some(fire),
+                    earth: Some(earth),
+                    water: Some(water),
+                    air: Some(air),
+                } => Ok(ElementalStats {
+                    fire,
+                    earth,
+                    water,
+                    air,
+                }),
+                _ => {
+                    let missing: Vec<_> = stats
+                        .iter()
+                        .filter(|(_, value)| value.is_none())
+                        .map(|(element, _)| format!("`{}{element}`", self.prefix))
+                        .collect();
+                    Err(de::Error::custom(format_args!(
+                        "missing field {}",
+                        missing.join(", ")
+                    )))
+                }
+            }
+        }
+    }
+
+    deserializer.deserialize_map(PrefixedVisitor {
+        prefix,
+        marker: PhantomData,
+    })
+}
+
+macro_rules! prefixed {
+    ($(#[$meta:meta])* $name:ident, $prefix:literal) => {
+        $(#[$meta])*
+        pub mod $name {
+            use super::*;
+
+            pub fn serialize<T: Serialize, S: Serializer>(
+                stats: &ElementalStats<T>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serialize_prefixed(stats, $prefix, serializer)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<ElementalStats<T>, D::Error>
+            where
+                T: Deserialize<'de>,
+                D: Deserializer<'de>,
+            {
+                deserialize_prefixed($prefix, deserializer)
+            }
+        }
+    };
+}
+
+prefixed!(
+    /// Flat `attack_<element>` fields.
+    attack,
+    "attack_"
+);
+prefixed!(
+    /// Flat `dmg_<element>` fields.
+    dmg,
+    "dmg_"
+);
+prefixed!(
+    /// Flat `res_<element>` fields.
+    res,
+    "res_"
+);
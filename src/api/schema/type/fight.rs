@@ -1,5 +1,6 @@
-use super::item::ItemComponent;
+use super::{elemental::ElementalStats, item::ItemComponent};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Fight {
@@ -23,29 +24,306 @@ pub struct Fight {
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BlockedHits {
-    /// The amount of fire hits blocked.
-    pub fire: i32,
-    /// The amount of earth hits blocked.
-    pub earth: i32,
-    /// The amount of water hits blocked.
-    pub water: i32,
-    /// The amount of air hits blocked.
-    pub air: i32,
+    /// The amount of hits blocked per element.
+    #[serde(flatten)]
+    pub hits: ElementalStats<i32>,
     /// The amount of total hits blocked.
     pub total: i32,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FightResult {
     Win,
     Lose,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum Element {
-    Fire,
-    Earth,
-    Water,
-    Air,
+with_all! {
+    /// The four elements of attacks, damage bonuses and resistances, in wire
+    /// order.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum Element {
+        Fire,
+        Earth,
+        Water,
+        Air,
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_plain::to_string(self).unwrap())
+    }
+}
+
+impl std::str::FromStr for Element {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_plain::from_str(&s.to_lowercase()).map_err(|_| format!("Unknown element: {}", s))
+    }
+}
+
+/// One side of a fight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Combatant {
+    Character,
+    Monster,
+}
+
+impl Combatant {
+    /// The other side of the fight.
+    pub fn opponent(&self) -> Combatant {
+        match self {
+            Combatant::Character => Combatant::Monster,
+            Combatant::Monster => Combatant::Character,
+        }
+    }
+}
+
+/// A single parsed line of [`Fight::logs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FightEvent {
+    /// Opening line with the starting HP of both sides.
+    Start { character_hp: i32, monster_hp: i32 },
+    /// An elemental attack that hit.
+    Attack {
+        turn: u32,
+        attacker: Combatant,
+        element: Element,
+        damage: i32,
+        critical: bool,
+    },
+    /// An elemental attack that was blocked by `defender`.
+    Block {
+        turn: u32,
+        defender: Combatant,
+        element: Element,
+    },
+    /// HP restored, e.g. by a potion in a utility slot.
+    Heal {
+        turn: u32,
+        target: Combatant,
+        amount: i32,
+    },
+    /// Damage over time such as burn or poison.
+    Effect {
+        turn: u32,
+        target: Combatant,
+        name: String,
+        damage: i32,
+    },
+    /// Closing line of the fight.
+    Result(FightResult),
+    /// A line the parser does not understand.
+    Unknown(String),
+}
+
+impl FightEvent {
+    /// Parse a single fight log line.
+    pub fn parse(line: &str) -> FightEvent {
+        let lower = line.to_lowercase();
+
+        if lower.starts_with("fight start") {
+            if let (Some(character_hp), Some(monster_hp)) = (
+                number_after(&lower, "character hp: "),
+                number_after(&lower, "monster hp: "),
+            ) {
+                return FightEvent::Start {
+                    character_hp,
+                    monster_hp,
+                };
+            }
+        }
+        if lower.starts_with("fight result") {
+            if lower.contains("win") {
+                return FightEvent::Result(FightResult::Win);
+            }
+            if lower.contains("lose") || lower.contains("loss") {
+                return FightEvent::Result(FightResult::Lose);
+            }
+        }
+
+        let turn = number_after(&lower, "turn ").map(|turn| turn.max(0) as u32);
+        // The subject of a line is whichever side is named first.
+        let subject = [
+            ("the character", Combatant::Character),
+            ("the monster", Combatant::Monster),
+        ]
+        .into_iter()
+        .filter_map(|(name, combatant)| Some((lower.find(name)?, combatant)))
+        .min_by_key(|(at, _)| *at)
+        .map(|(_, combatant)| combatant);
+
+        if let (Some(turn), Some(subject)) = (turn, subject) {
+            let element = Element::ALL
+                .into_iter()
+                .find(|element| lower.contains(&format!("{element} attack")));
+
+            if let Some(element) = element {
+                if lower.contains("blocked") {
+                    return FightEvent::Block {
+                        turn,
+                        defender: subject,
+                        element,
+                    };
+                }
+                if let Some(damage) = number_after(&lower, "dealt ") {
+                    return FightEvent::Attack {
+                        turn,
+                        attacker: subject,
+                        element,
+                        damage,
+                        critical: lower.contains("critical"),
+                    };
+                }
+            }
+            if let Some(amount) = number_after(&lower, "restored ")
+                .or_else(|| number_after(&lower, "healed "))
+                .or_else(|| number_after(&lower, "recovered "))
+            {
+                return FightEvent::Heal {
+                    turn,
+                    target: subject,
+                    amount,
+                };
+            }
+            let effect = ["burn", "poison"]
+                .into_iter()
+                .find(|effect| lower.contains(effect));
+            let damage =
+                number_after(&lower, "suffered ").or_else(|| number_after(&lower, "dealt "));
+            if let (Some(effect), Some(damage)) = (effect, damage) {
+                return FightEvent::Effect {
+                    turn,
+                    target: subject,
+                    name: effect.to_string(),
+                    damage,
+                };
+            }
+        }
+
+        FightEvent::Unknown(line.to_string())
+    }
+}
+
+/// Parse the integer that directly follows `pattern` in `line`.
+fn number_after(line: &str, pattern: &str) -> Option<i32> {
+    let start = line.find(pattern)? + pattern.len();
+    let digits: String = line[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '-')
+        .collect();
+    digits.parse().ok()
+}
+
+/// Totals computed from the events of a fight.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FightSummary {
+    /// Damage dealt by the character, per element.
+    pub damage_dealt: ElementalStats<i32>,
+    /// Damage taken by the character, per element.
+    pub damage_taken: ElementalStats<i32>,
+    /// Damage dealt to the monster by burn or poison.
+    pub effect_damage_dealt: i32,
+    /// Damage taken by the character from burn or poison.
+    pub effect_damage_taken: i32,
+    /// HP restored to the character.
+    pub healed: i32,
+    /// Attacks blocked by the character, per element.
+    pub blocked_by_character: ElementalStats<i32>,
+    /// Attacks blocked by the monster, per element.
+    pub blocked_by_monster: ElementalStats<i32>,
+    /// Critical strikes landed by the character.
+    pub character_criticals: u32,
+    /// Critical strikes landed by the monster.
+    pub monster_criticals: u32,
+    /// Last turn seen in the logs.
+    pub turns: u32,
+    /// Result line of the fight, if present.
+    pub result: Option<FightResult>,
+}
+
+impl FromIterator<FightEvent> for FightSummary {
+    fn from_iter<I: IntoIterator<Item = FightEvent>>(iter: I) -> Self {
+        let mut summary = FightSummary::default();
+        for event in iter {
+            match event {
+                FightEvent::Attack {
+                    turn,
+                    attacker,
+                    element,
+                    damage,
+                    critical,
+                } => {
+                    summary.turns = summary.turns.max(turn);
+                    let (damage_stats, criticals) = match attacker {
+                        Combatant::Character => {
+                            (&mut summary.damage_dealt, &mut summary.character_criticals)
+                        }
+                        Combatant::Monster => {
+                            (&mut summary.damage_taken, &mut summary.monster_criticals)
+                        }
+                    };
+                    damage_stats[element] += damage;
+                    *criticals += u32::from(critical);
+                }
+                FightEvent::Block {
+                    turn,
+                    defender,
+                    element,
+                } => {
+                    summary.turns = summary.turns.max(turn);
+                    match defender {
+                        Combatant::Character => summary.blocked_by_character[element] += 1,
+                        Combatant::Monster => summary.blocked_by_monster[element] += 1,
+                    }
+                }
+                FightEvent::Heal {
+                    turn,
+                    target,
+                    amount,
+                } => {
+                    summary.turns = summary.turns.max(turn);
+                    if target == Combatant::Character {
+                        summary.healed += amount;
+                    }
+                }
+                FightEvent::Effect {
+                    turn,
+                    target,
+                    damage,
+                    ..
+                } => {
+                    summary.turns = summary.turns.max(turn);
+                    match target {
+                        Combatant::Character => summary.effect_damage_taken += damage,
+                        Combatant::Monster => summary.effect_damage_dealt += damage,
+                    }
+                }
+                FightEvent::Result(result) => summary.result = Some(result),
+                FightEvent::Start { .. } | FightEvent::Unknown(_) => {}
+            }
+        }
+        summary
+    }
+}
+
+impl Fight {
+    /// Parse the fight logs into structured events.
+    pub fn events(&self) -> Vec<FightEvent> {
+        self.logs
+            .iter()
+            .map(|line| FightEvent::parse(line))
+            .collect()
+    }
+
+    /// Summarize the fight logs.
+    pub fn summary(&self) -> FightSummary {
+        self.logs
+            .iter()
+            .map(|line| FightEvent::parse(line))
+            .collect()
+    }
 }
@@ -1,9 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use super::character::Skill;
-
-/// A type alias for an item code, which is represented as a `String` with match pattern `^[a-zA-Z0-9_-]+$`.
-pub type ItemCode = String;
+pub use super::code::ItemCode;
+use super::{code::ResourceCode, effect::Effect};
 
 /// Represents an item.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,7 +15,7 @@ pub struct Item {
     #[serde(rename = "subtype")] // Corrected from sub_type to subtype
     pub sub_type: Option<ItemSubType>,
     pub description: String,
-    pub effects: Vec<ItemEffect>, // Need to handle this better
+    pub effects: Vec<Effect>,
     pub craft: Option<Craft>,
 }
 
@@ -24,7 +23,7 @@ pub struct Item {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Resource {
     pub name: String,
-    pub code: ItemCode,
+    pub code: ResourceCode,
     pub skill: Skill,
     pub level: i32,
     pub drops: Vec<ResourceDrop>,
@@ -54,28 +53,15 @@ pub struct Recycle {
     pub items: Vec<ItemComponent>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Wire representation of an item effect, see [`Effect`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ItemEffect {
     pub name: String,
     pub value: i32,
 }
 
-impl PartialEq for ItemEffect {
-    fn eq(&self, other: &Self) -> bool {
-        self.name == other.name
-    }
-}
-
-impl Eq for ItemEffect {}
-
-impl std::hash::Hash for ItemEffect {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.name.hash(state);
-    }
-}
-
 /// Represents an item drop.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ItemComponent {
     /// The code of the item.
     pub code: ItemCode,
@@ -190,45 +176,51 @@ impl ItemType {
     }
 }
 
-/// Represents the various item slots available for a character.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum ItemSlot {
-    /// The weapon slot.
-    Weapon,
-    /// The shield slot.
-    Shield,
-    /// The helmet slot.
-    Helmet,
-    /// The body armor slot.
-    BodyArmor,
-    /// The leg armor slot.
-    LegArmor,
-    /// The boots slot.
-    Boots,
-    /// The first ring slot.
-    Ring1,
-    /// The second ring slot.
-    Ring2,
-    /// The amulet slot.
-    Amulet,
-    /// The first artifact slot.
-    Artifact1,
-    /// The second artifact slot.
-    Artifact2,
-    /// The third artifact slot.
-    Artifact3,
-    /// The first consumable slot.
-    Utility1,
-    /// The second consumable slot.
-    Utility2,
-    /// Undefined slot.
-    Undefined,
+with_all! {
+    /// Represents the various item slots available for a character.
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ItemSlot {
+        /// The weapon slot.
+        Weapon,
+        /// The shield slot.
+        Shield,
+        /// The helmet slot.
+        Helmet,
+        /// The body armor slot.
+        BodyArmor,
+        /// The leg armor slot.
+        LegArmor,
+        /// The boots slot.
+        Boots,
+        /// The first ring slot.
+        Ring1,
+        /// The second ring slot.
+        Ring2,
+        /// The amulet slot.
+        Amulet,
+        /// The first artifact slot.
+        Artifact1,
+        /// The second artifact slot.
+        Artifact2,
+        /// The third artifact slot.
+        Artifact3,
+        /// The first consumable slot.
+        Utility1,
+        /// The second consumable slot.
+        Utility2,
+    }
+    // Not a slot a character can hold an item in.
+    except {
+        /// Undefined slot.
+        Undefined,
+    }
 }
 
 impl ItemSlot {
-    pub fn to_item_type(&self) -> ItemType {
-        match self {
+    /// Type of the items this slot holds, `None` for [`ItemSlot::Undefined`].
+    pub fn to_item_type(&self) -> Option<ItemType> {
+        let r#type = match self {
             ItemSlot::Weapon => ItemType::Weapon,
             ItemSlot::Shield => ItemType::Shield,
             ItemSlot::Helmet => ItemType::Helmet,
@@ -238,8 +230,10 @@ impl ItemSlot {
             ItemSlot::Ring1 | ItemSlot::Ring2 => ItemType::Ring,
             ItemSlot::Amulet => ItemType::Amulet,
             ItemSlot::Artifact1 | ItemSlot::Artifact2 | ItemSlot::Artifact3 => ItemType::Artifact,
-            _ => panic!("Unhandled item slot!"),
-        }
+            ItemSlot::Utility1 | ItemSlot::Utility2 => ItemType::Utility,
+            ItemSlot::Undefined => return None,
+        };
+        Some(r#type)
     }
 }
 
@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{
+    character::Position,
+    code::{MapCode, MonsterCode, NpcCode, ResourceCode},
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Map {
@@ -13,15 +19,52 @@ pub struct Map {
     pub content: Option<MapContent>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl Map {
+    /// Coordinates of the map.
+    pub fn position(&self) -> Position {
+        Position::new(self.x, self.y)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MapContent {
     /// Type of the content.
     pub r#type: MapContentType,
     /// Code of the content.
-    pub code: String,
+    pub code: MapCode,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+impl MapContent {
+    pub fn new(r#type: MapContentType, code: MapCode) -> Self {
+        Self { r#type, code }
+    }
+
+    /// The monster living on this tile, if any.
+    pub fn monster(&self) -> Option<MonsterCode> {
+        match self.r#type {
+            MapContentType::Monster => self.code.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The resource that can be gathered on this tile, if any.
+    pub fn resource(&self) -> Option<ResourceCode> {
+        match self.r#type {
+            MapContentType::Resource => self.code.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The NPC standing on this tile, if any.
+    pub fn npc(&self) -> Option<NpcCode> {
+        match self.r#type {
+            MapContentType::Npc => self.code.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum MapContentType {
     Monster,
@@ -34,4 +77,11 @@ pub enum MapContentType {
     TasksMaster,
     #[serde(rename = "santa_claus")]
     SantaClaus,
+    Npc,
+}
+
+impl fmt::Display for MapContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_plain::to_string(self).unwrap())
+    }
 }
@@ -1,6 +1,38 @@
+/// Declare an enum along with an `ALL` constant listing its variants in
+/// declaration order, so that the list cannot miss one. Variants declared in
+/// a trailing `except` block are left out of it.
+macro_rules! with_all {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident,)+
+        }
+        $(
+            except {
+                $($(#[$other_meta:meta])* $other:ident,)+
+            }
+        )?
+    ) => {
+        $(#[$meta])*
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            $($($(#[$other_meta])* $other,)+)?
+        }
+
+        impl $name {
+            /// Every variant, in declaration order.
+            pub const ALL: [$name; [$(stringify!($variant)),+].len()] = [$($name::$variant),+];
+        }
+    };
+}
+
 pub mod bank;
 pub mod character;
+pub mod code;
+pub mod effect;
+pub mod elemental;
 pub mod fight;
 pub mod item;
 pub mod map;
 pub mod monster;
+pub mod time;
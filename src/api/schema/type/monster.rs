@@ -1,4 +1,8 @@
-use super::item::ItemCode;
+use super::{
+    code::MonsterCode,
+    elemental::{self, ElementalStats},
+    item::ItemCode,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -8,27 +12,17 @@ pub struct Monster {
     /// Name of the monster.
     pub name: String,
     /// The code of the monster. This is the monster's unique identifier (ID).
-    pub code: String,
+    pub code: MonsterCode,
     /// The level of the monster.
     pub level: i32,
     /// The hit points (HP) of the monster.
     pub hp: i32,
-    /// Monster's fire attack strength.
-    pub attack_fire: i32,
-    /// Monster's earth attack strength.
-    pub attack_earth: i32,
-    /// Monster's water attack strength.
-    pub attack_water: i32,
-    /// Monster's air attack strength.
-    pub attack_air: i32,
-    /// Percentage of resistance to fire attacks.
-    pub res_fire: i32,
-    /// Percentage of resistance to earth attacks.
-    pub res_earth: i32,
-    /// Percentage of resistance to water attacks.
-    pub res_water: i32,
-    /// Percentage of resistance to air attacks.
-    pub res_air: i32,
+    /// Monster's elemental attack strength.
+    #[serde(flatten, with = "elemental::attack")]
+    pub attack: ElementalStats<i32>,
+    /// Percentage of resistance to elemental attacks.
+    #[serde(flatten, with = "elemental::res")]
+    pub res: ElementalStats<i32>,
     /// The minimum amount of gold dropped by the monster upon defeat.
     pub min_gold: i32,
     /// The maximum amount of gold dropped by the monster upon defeat.
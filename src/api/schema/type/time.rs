@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::Deref, time::Duration};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// A datetime sent by the server in ISO 8601 format.
+///
+/// The string sent is kept as is, and the type is the same whatever the
+/// features enabled, so that enabling `chrono` never changes the fields of
+/// the schema. With the `chrono` feature, `to_datetime` reads it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Timestamp(String);
+
+impl Timestamp {
+    pub fn new(timestamp: impl Into<String>) -> Self {
+        Self(timestamp.into())
+    }
+
+    /// The timestamp as sent by the server.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwrap the underlying string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    /// Read the timestamp, `None` when it is not a valid ISO 8601 datetime.
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        self.0.parse().ok()
+    }
+}
+
+impl From<String> for Timestamp {
+    fn from(timestamp: String) -> Self {
+        Self(timestamp)
+    }
+}
+
+impl From<&str> for Timestamp {
+    fn from(timestamp: &str) -> Self {
+        Self(timestamp.to_string())
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Deref for Timestamp {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Timestamp {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Timestamp {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Timestamp {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Local estimate of the server clock.
+///
+/// Cooldown expirations are server timestamps, so waiting on them with a
+/// drifting local clock wakes up too early or too late. The skew is the
+/// offset to add to the local time to get the server time; the default clock
+/// has none. Timestamps can only be read with the `chrono` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ServerClock {
+    #[cfg(feature = "chrono")]
+    skew: TimeDelta,
+}
+
+impl ServerClock {
+    /// Build a clock from a server time observed at `local_now`.
+    #[cfg(feature = "chrono")]
+    pub fn new(server_time: DateTime<Utc>, local_now: DateTime<Utc>) -> Self {
+        Self {
+            skew: server_time - local_now,
+        }
+    }
+
+    /// Offset between the server and the local clock.
+    #[cfg(feature = "chrono")]
+    pub fn skew(&self) -> TimeDelta {
+        self.skew
+    }
+
+    /// Current server time, as estimated from the local clock.
+    #[cfg(feature = "chrono")]
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.skew
+    }
+
+    /// Time left until a server timestamp, zero once it has passed. `None`
+    /// when the timestamp cannot be read, which is always the case without
+    /// the `chrono` feature.
+    pub fn until(&self, timestamp: &Timestamp) -> Option<Duration> {
+        #[cfg(feature = "chrono")]
+        {
+            let timestamp = timestamp.to_datetime()?;
+            Some((timestamp - self.now()).to_std().unwrap_or_default())
+        }
+        #[cfg(not(feature = "chrono"))]
+        {
+            let _ = timestamp;
+            None
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+use tokio::time::Instant;
+
+use crate::api::{
+    schema::{
+        r#type::item::{ItemCode, ItemComponent},
+        response::{my_account::AccountBankDetailsData, ResponseData, ResponseError},
+    },
+    Api,
+};
+
+/// A difference between the local mirror and the server, found when
+/// reconciling.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Drift {
+    Item {
+        code: ItemCode,
+        expected: u32,
+        actual: u32,
+    },
+    Gold {
+        expected: u32,
+        actual: u32,
+    },
+    Slots {
+        expected: u32,
+        actual: u32,
+    },
+}
+
+/// Local mirror of the bank, kept up to date from action responses.
+///
+/// Deposits and withdrawals return enough to update the mirror without
+/// fetching the bank again. An expansion does not return the new slot count
+/// or the cost of the next one, so it leaves the mirror stale. Changes made
+/// elsewhere, e.g. by the grand exchange or another program, are only seen
+/// when reconciling.
+///
+/// Nothing reconciles the mirror on its own: the owner should call
+/// [`sync`](Self::sync), or [`Fleet::sync_bank`](crate::fleet::Fleet::sync_bank),
+/// whenever [`needs_sync`](Self::needs_sync) says so, or let
+/// [`Fleet::sync_bank_every`](crate::fleet::Fleet::sync_bank_every) do it.
+///
+/// Only the content is serialized: a deserialized mirror has never been
+/// synced, and needs reconciling before it is trusted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BankState {
+    items: BTreeMap<ItemCode, u32>,
+    gold: u32,
+    slots: u32,
+    expansions: u32,
+    next_expansion_cost: u32,
+    #[serde(skip)]
+    synced_at: Option<Instant>,
+}
+
+impl BankState {
+    pub fn new(details: &AccountBankDetailsData, items: &[ItemComponent]) -> Self {
+        let mut bank = Self {
+            items: BTreeMap::new(),
+            gold: 0,
+            slots: 0,
+            expansions: 0,
+            next_expansion_cost: 0,
+            synced_at: None,
+        };
+        bank.reconcile(details, items);
+        bank
+    }
+
+    /// Seed the mirror from the server.
+    pub async fn fetch(api: &Api) -> Result<Self, ResponseError> {
+        let details = api.bank_details().await?;
+        let items = api.bank_items().await?;
+        Ok(Self::new(&details, &items))
+    }
+
+    pub fn quantity(&self, code: &str) -> u32 {
+        self.items.get(code).copied().unwrap_or_default()
+    }
+
+    /// Iterate over the items stored, by code.
+    pub fn items(&self) -> impl Iterator<Item = (&ItemCode, u32)> {
+        self.items.iter().map(|(code, quantity)| (code, *quantity))
+    }
+
+    /// The items stored, in the format of `Api::bank_items`.
+    pub fn to_components(&self) -> Vec<ItemComponent> {
+        self.items()
+            .map(|(code, quantity)| ItemComponent {
+                code: code.clone(),
+                quantity,
+            })
+            .collect()
+    }
+
+    pub fn gold(&self) -> u32 {
+        self.gold
+    }
+
+    pub fn slots(&self) -> u32 {
+        self.slots
+    }
+
+    /// Slots holding no item.
+    pub fn free_slots(&self) -> u32 {
+        self.slots.saturating_sub(self.items.len() as u32)
+    }
+
+    pub fn expansions(&self) -> u32 {
+        self.expansions
+    }
+
+    /// Cost of the next expansion as of the last reconciliation.
+    pub fn next_expansion_cost(&self) -> u32 {
+        self.next_expansion_cost
+    }
+
+    /// Apply the result of an action, returning whether it changed the bank.
+    pub fn update(&mut self, data: &ResponseData) -> bool {
+        match data {
+            ResponseData::CharacterItemTransaction(data) => {
+                self.items = data
+                    .bank
+                    .iter()
+                    .filter(|item| item.quantity > 0)
+                    .map(|item| (item.code.clone(), item.quantity))
+                    .collect();
+            }
+            ResponseData::CharacterGoldTransaction(data) => self.gold = data.bank.quantity,
+            ResponseData::CharacterBankExpansion(_) => {
+                // Slots and the next cost are only known after a sync.
+                self.expansions += 1;
+                self.synced_at = None;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether the last reconciliation is older than `every`, or there was
+    /// none.
+    pub fn needs_sync(&self, every: Duration) -> bool {
+        self.synced_at
+            .is_none_or(|synced_at| synced_at.elapsed() >= every)
+    }
+
+    /// Replace the mirror with the content fetched from the server,
+    /// returning every difference found, items sorted by code.
+    pub fn reconcile(
+        &mut self,
+        details: &AccountBankDetailsData,
+        items: &[ItemComponent],
+    ) -> Vec<Drift> {
+        let mut actual: BTreeMap<ItemCode, u32> = BTreeMap::new();
+        for item in items.iter().filter(|item| item.quantity > 0) {
+            *actual.entry(item.code.clone()).or_default() += item.quantity;
+        }
+
+        let mut drift = Vec::new();
+        let mut codes: Vec<_> = self.items.keys().chain(actual.keys()).collect();
+        codes.sort();
+        codes.dedup();
+        for code in codes {
+            let expected = self.quantity(code);
+            let actual = actual.get(code).copied().unwrap_or_default();
+            if expected != actual {
+                drift.push(Drift::Item {
+                    code: code.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if self.gold != details.gold {
+            drift.push(Drift::Gold {
+                expected: self.gold,
+                actual: details.gold,
+            });
+        }
+        if self.slots != details.slots {
+            drift.push(Drift::Slots {
+                expected: self.slots,
+                actual: details.slots,
+            });
+        }
+
+        self.items = actual;
+        self.gold = details.gold;
+        self.slots = details.slots;
+        self.expansions = details.expansions;
+        self.next_expansion_cost = details.next_expansion_cost;
+        self.synced_at = Some(Instant::now());
+        drift
+    }
+
+    /// Fetch the bank and reconcile the mirror with it.
+    pub async fn sync(&mut self, api: &Api) -> Result<Vec<Drift>, ResponseError> {
+        let details = api.bank_details().await?;
+        let items = api.bank_items().await?;
+        Ok(self.reconcile(&details, &items))
+    }
+}
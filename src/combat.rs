@@ -0,0 +1,299 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::api::schema::r#type::{
+    character::Character,
+    effect::Effect,
+    elemental::ElementalStats,
+    fight::Element,
+    item::{Item, ItemCode, ItemSlot},
+    monster::Monster,
+};
+
+/// Fights end in a loss for the character after this many turns.
+pub const MAX_TURNS: u32 = 100;
+
+/// Fight cooldown per turn, before haste.
+pub const SECONDS_PER_TURN: u32 = 2;
+
+/// A potion equipped in a utility slot that restores HP during fights.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Restore {
+    /// HP restored per use.
+    pub hp: i32,
+    /// Number of potions available.
+    pub quantity: u32,
+}
+
+/// Combat stats of one side of a fight.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fighter {
+    pub hp: i32,
+    pub max_hp: i32,
+    pub attack: ElementalStats<i32>,
+    pub dmg: ElementalStats<i32>,
+    pub res: ElementalStats<i32>,
+    pub critical_strike: i32,
+    pub haste: i32,
+    pub restores: Vec<Restore>,
+}
+
+impl Fighter {
+    /// Stats of a character, including the boost and restore potions
+    /// equipped in its utility slots.
+    ///
+    /// Utilities missing from `items` are ignored.
+    pub fn from_character(character: &Character, items: &HashMap<ItemCode, Item>) -> Self {
+        let stats = &character.combat_stats;
+        let elemental = &character.elemental_attributes;
+        let mut fighter = Self {
+            hp: stats.hp,
+            max_hp: stats.max_hp,
+            attack: elemental.attack,
+            dmg: elemental.dmg,
+            res: elemental.res,
+            critical_strike: stats.critical_strike,
+            haste: stats.haste,
+            restores: Vec::new(),
+        };
+
+        for slot in [ItemSlot::Utility1, ItemSlot::Utility2] {
+            let quantity = character.equipment.quantity(&slot);
+            let Some(item) = character
+                .equipment
+                .get(&slot)
+                .and_then(|code| items.get(code))
+            else {
+                continue;
+            };
+            if quantity == 0 {
+                continue;
+            }
+            for effect in &item.effects {
+                match effect {
+                    Effect::Restore(hp) => fighter.restores.push(Restore { hp: *hp, quantity }),
+                    Effect::BoostHp(hp) => {
+                        fighter.hp += hp;
+                        fighter.max_hp += hp;
+                    }
+                    Effect::BoostDmg(element, value) => fighter.dmg[*element] += value,
+                    Effect::BoostRes(element, value) => fighter.res[*element] += value,
+                    _ => {}
+                }
+            }
+        }
+        fighter
+    }
+
+    pub fn from_monster(monster: &Monster) -> Self {
+        Self {
+            hp: monster.hp,
+            max_hp: monster.hp,
+            attack: monster.attack,
+            res: monster.res,
+            ..Default::default()
+        }
+    }
+
+    /// Damage of a non critical hit of each element against `defender`.
+    pub fn hit_damage(&self, defender: &Fighter) -> ElementalStats<i32> {
+        ElementalStats::from_fn(|element| {
+            let attack = self.attack[element];
+            if attack <= 0 {
+                return 0;
+            }
+            let boosted =
+                (f64::from(attack) * (1.0 + f64::from(self.dmg[element]) / 100.0)).round();
+            let reduced = boosted * (1.0 - f64::from(defender.res[element]) / 100.0);
+            reduced.round().max(0.0) as i32
+        })
+    }
+
+    /// Average damage dealt per turn to `defender`, accounting for blocks and
+    /// critical strikes.
+    pub fn expected_damage(&self, defender: &Fighter) -> f64 {
+        let critical = critical_chance(self.critical_strike);
+        self.hit_damage(defender)
+            .iter()
+            .map(|(element, damage)| {
+                let hit = 1.0 - block_chance(defender.res[element]);
+                f64::from(*damage) * hit * (1.0 + critical * 0.5)
+            })
+            .sum()
+    }
+
+    /// Fight cooldown for a fight lasting `turns` turns.
+    pub fn cooldown(&self, turns: u32) -> Duration {
+        let seconds = f64::from(turns * SECONDS_PER_TURN);
+        let haste = f64::from(self.haste.clamp(0, 100)) / 100.0;
+        Duration::from_secs_f64((seconds * (1.0 - haste)).round())
+    }
+}
+
+/// Chance for a hit to be blocked, assumed to be 1% per 10% of resistance.
+fn block_chance(res: i32) -> f64 {
+    (f64::from(res.max(0)) / 1000.0).min(1.0)
+}
+
+fn critical_chance(critical_strike: i32) -> f64 {
+    (f64::from(critical_strike.max(0)) / 100.0).min(1.0)
+}
+
+/// Result of a single simulated fight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FightOutcome {
+    pub won: bool,
+    pub turns: u32,
+    /// HP of the character at the end of the fight.
+    pub hp: i32,
+    /// Restore potions consumed.
+    pub restores_used: u32,
+}
+
+/// Aggregated prediction over many simulated fights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CombatPrediction {
+    pub win_probability: f64,
+    pub expected_turns: f64,
+    pub expected_hp_lost: f64,
+    pub expected_cooldown: Duration,
+    pub expected_restores_used: f64,
+}
+
+/// Monte Carlo fight simulator.
+///
+/// Each turn the attacker hits with every element it has attack in. A hit is
+/// `attack × (1 + dmg%) × (1 − res%)`, is blocked with a chance of 1% per
+/// 10% of resistance and deals 50% more damage on a critical strike. The side
+/// with the most haste attacks first, the character winning ties. Restore
+/// potions are used at the start of the character turn when its HP drops
+/// below half.
+///
+/// These rules are the crate's own model of the game and have not been
+/// checked against fights recorded from the server. The critical strike
+/// rolled once per turn for every element and the block chance in particular
+/// are assumptions.
+#[derive(Clone, Copy, Debug)]
+pub struct CombatSimulator {
+    runs: u32,
+    seed: u64,
+}
+
+impl Default for CombatSimulator {
+    fn default() -> Self {
+        Self {
+            runs: 1000,
+            seed: 0x5eed,
+        }
+    }
+}
+
+impl CombatSimulator {
+    pub fn new(runs: u32, seed: u64) -> Self {
+        Self {
+            runs: runs.max(1),
+            seed,
+        }
+    }
+
+    /// Simulate the fight many times and average the outcomes.
+    pub fn simulate(&self, character: &Fighter, monster: &Fighter) -> CombatPrediction {
+        let mut rng = SplitMix64(self.seed);
+        let (mut wins, mut turns, mut hp_lost, mut cooldown, mut restores) =
+            (0u32, 0u64, 0i64, Duration::ZERO, 0u64);
+
+        for _ in 0..self.runs {
+            let outcome = fight(character, monster, &mut || rng.next_f64());
+            wins += u32::from(outcome.won);
+            turns += u64::from(outcome.turns);
+            hp_lost += i64::from(character.hp - outcome.hp);
+            cooldown += character.cooldown(outcome.turns);
+            restores += u64::from(outcome.restores_used);
+        }
+
+        let runs = f64::from(self.runs);
+        CombatPrediction {
+            win_probability: f64::from(wins) / runs,
+            expected_turns: turns as f64 / runs,
+            expected_hp_lost: hp_lost as f64 / runs,
+            expected_cooldown: cooldown / self.runs,
+            expected_restores_used: restores as f64 / runs,
+        }
+    }
+
+    /// Simulate a fight where no hit is blocked or critical.
+    pub fn simulate_average(&self, character: &Fighter, monster: &Fighter) -> FightOutcome {
+        // 0.999… never falls under a block or critical chance below 100%.
+        fight(character, monster, &mut || 1.0 - f64::EPSILON)
+    }
+}
+
+/// Run a single fight, drawing random numbers in `[0, 1)` from `roll`.
+pub fn fight(
+    character: &Fighter,
+    monster: &Fighter,
+    roll: &mut impl FnMut() -> f64,
+) -> FightOutcome {
+    let mut hp = [character.hp, monster.hp];
+    let fighters = [character, monster];
+    let damage = [character.hit_damage(monster), monster.hit_damage(character)];
+    let mut restores = character.restores.clone();
+    let mut restores_used = 0;
+    let mut attacker = usize::from(monster.haste > character.haste);
+
+    for turn in 1..=MAX_TURNS {
+        let defender = 1 - attacker;
+
+        if attacker == 0 && hp[0] < character.max_hp / 2 {
+            if let Some(restore) = restores.iter_mut().find(|restore| restore.quantity > 0) {
+                restore.quantity -= 1;
+                restores_used += 1;
+                hp[0] = (hp[0] + restore.hp).min(character.max_hp);
+            }
+        }
+
+        // Assumed: one critical roll per turn, applying to every element.
+        let critical = roll() < critical_chance(fighters[attacker].critical_strike);
+        for element in Element::ALL {
+            let hit = damage[attacker][element];
+            if hit == 0 || roll() < block_chance(fighters[defender].res[element]) {
+                continue;
+            }
+            hp[defender] -= if critical { hit + hit / 2 } else { hit };
+        }
+
+        if hp[defender] <= 0 {
+            return FightOutcome {
+                won: defender == 1,
+                turns: turn,
+                hp: hp[0].max(0),
+                restores_used,
+            };
+        }
+        attacker = defender;
+    }
+
+    FightOutcome {
+        won: false,
+        turns: MAX_TURNS,
+        hp: hp[0].max(0),
+        restores_used,
+    }
+}
+
+/// Small deterministic generator so simulations are reproducible without
+/// pulling in a random number crate.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use thiserror::Error;
+
+use crate::api::schema::r#type::{
+    character::{InventoryInfo, Skill},
+    item::{Item, ItemCode, ItemComponent},
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CraftingError {
+    #[error("recipe of {0} depends on itself")]
+    Cycle(ItemCode),
+    #[error("recipe of {0} produces nothing")]
+    EmptyRecipe(ItemCode),
+}
+
+/// Items already owned, summed over the bank and inventories.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stock(BTreeMap<ItemCode, u32>);
+
+impl Stock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the content of the bank, as returned by `Api::bank_items`.
+    pub fn with_bank(mut self, bank: &[ItemComponent]) -> Self {
+        bank.iter()
+            .for_each(|item| self.add(item.code.clone(), item.quantity));
+        self
+    }
+
+    /// Add the content of a character inventory.
+    pub fn with_inventory(mut self, inventory: &InventoryInfo) -> Self {
+        inventory
+            .items()
+            .for_each(|(code, quantity)| self.add(code, quantity));
+        self
+    }
+
+    pub fn add(&mut self, code: ItemCode, quantity: u32) {
+        *self.0.entry(code).or_default() += quantity;
+    }
+
+    /// Remove up to `quantity` items, returning how many were actually taken.
+    pub fn take(&mut self, code: &ItemCode, quantity: u32) -> u32 {
+        let Some(available) = self.0.get_mut(code) else {
+            return 0;
+        };
+        let taken = quantity.min(*available);
+        *available -= taken;
+        taken
+    }
+
+    pub fn quantity(&self, code: &str) -> u32 {
+        self.0.get(code).copied().unwrap_or_default()
+    }
+
+    /// Iterate over the items held by code, skipping those taken entirely.
+    pub fn iter(&self) -> impl Iterator<Item = (&ItemCode, u32)> {
+        self.0
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .map(|(code, quantity)| (code, *quantity))
+    }
+}
+
+/// Full recipe tree of an item, independent of what is already owned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecipeNode {
+    /// Item produced by this node.
+    pub code: ItemCode,
+    /// Quantity of the item needed by the parent.
+    pub quantity: u32,
+    /// Craft step producing the item, `None` for raw materials.
+    pub step: Option<CraftStep>,
+    /// Ingredients of the craft.
+    pub children: Vec<RecipeNode>,
+}
+
+impl RecipeNode {
+    /// Depth-first iteration over the tree, starting with this node.
+    pub fn iter(&self) -> impl Iterator<Item = &RecipeNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// A single craft action repeated `crafts` times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CraftStep {
+    /// Item crafted.
+    pub code: ItemCode,
+    /// Number of craft actions.
+    pub crafts: u32,
+    /// Number of items produced.
+    pub quantity: u32,
+    /// Skill used by the craft.
+    pub skill: Skill,
+    /// Skill level required by the craft.
+    pub level: u32,
+}
+
+/// What it takes to obtain a quantity of an item.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CraftingPlan {
+    /// Crafts to perform, ingredients always before the items using them.
+    pub steps: Vec<CraftStep>,
+    /// Raw materials needed when starting from nothing.
+    pub raw_materials: BTreeMap<ItemCode, u32>,
+    /// Raw materials still to be gathered or bought once stock is used.
+    pub missing: BTreeMap<ItemCode, u32>,
+    /// Items taken from stock, raw or crafted.
+    pub from_stock: BTreeMap<ItemCode, u32>,
+}
+
+impl CraftingPlan {
+    /// Highest level required per skill by the steps of the plan.
+    pub fn required_levels(&self) -> BTreeMap<Skill, u32> {
+        let mut levels = BTreeMap::new();
+        for step in &self.steps {
+            let level = levels.entry(step.skill).or_default();
+            *level = step.level.max(*level);
+        }
+        levels
+    }
+
+    /// Whether every material is available from stock.
+    pub fn is_ready(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Expands crafting recipes over an item catalogue.
+#[derive(Clone, Copy, Debug)]
+pub struct CraftingPlanner<'a> {
+    items: &'a HashMap<ItemCode, Item>,
+}
+
+impl<'a> CraftingPlanner<'a> {
+    pub fn new(items: &'a HashMap<ItemCode, Item>) -> Self {
+        Self { items }
+    }
+
+    fn step(&self, code: &ItemCode, needed: u32) -> Result<Option<CraftStep>, CraftingError> {
+        let Some(craft) = self.items.get(code).and_then(|item| item.craft.as_ref()) else {
+            return Ok(None);
+        };
+        if craft.quantity == 0 {
+            return Err(CraftingError::EmptyRecipe(code.clone()));
+        }
+        let crafts = needed.div_ceil(craft.quantity);
+        Ok(Some(CraftStep {
+            code: code.clone(),
+            crafts,
+            quantity: crafts * craft.quantity,
+            skill: craft.skill,
+            level: craft.level,
+        }))
+    }
+
+    /// Expand the full recipe tree of `target`.
+    pub fn tree(&self, target: &ItemComponent) -> Result<RecipeNode, CraftingError> {
+        self.tree_inner(target, &mut Vec::new())
+    }
+
+    fn tree_inner(
+        &self,
+        target: &ItemComponent,
+        path: &mut Vec<ItemCode>,
+    ) -> Result<RecipeNode, CraftingError> {
+        if path.contains(&target.code) {
+            return Err(CraftingError::Cycle(target.code.clone()));
+        }
+        let step = self.step(&target.code, target.quantity)?;
+        let mut children = Vec::new();
+        if let Some(step) = &step {
+            let craft = self.items[&target.code].craft.as_ref().unwrap();
+            path.push(target.code.clone());
+            for component in &craft.items {
+                children.push(self.tree_inner(
+                    &ItemComponent {
+                        code: component.code.clone(),
+                        quantity: component.quantity * step.crafts,
+                    },
+                    path,
+                )?);
+            }
+            path.pop();
+        }
+
+        Ok(RecipeNode {
+            code: target.code.clone(),
+            quantity: target.quantity,
+            step,
+            children,
+        })
+    }
+
+    /// Plan how to obtain `target`, using what is in `stock` first.
+    ///
+    /// Intermediate items found in stock are used as-is instead of being
+    /// crafted again.
+    pub fn plan(
+        &self,
+        target: &ItemComponent,
+        stock: &Stock,
+    ) -> Result<CraftingPlan, CraftingError> {
+        let order = self.topological_order(&target.code)?;
+        let raw_materials = self.resolve(&order, target, &mut Stock::new())?.missing;
+        let mut plan = self.resolve(&order, target, &mut stock.clone())?;
+        plan.raw_materials = raw_materials;
+        Ok(plan)
+    }
+
+    /// Propagate demand from `target` down to raw materials, visiting items
+    /// so that every user of an item is handled before the item itself.
+    fn resolve(
+        &self,
+        order: &[ItemCode],
+        target: &ItemComponent,
+        stock: &mut Stock,
+    ) -> Result<CraftingPlan, CraftingError> {
+        let mut plan = CraftingPlan::default();
+        let mut demand = HashMap::from([(target.code.clone(), target.quantity)]);
+
+        for code in order.iter().rev() {
+            let needed = demand.remove(code).unwrap_or_default();
+            if needed == 0 {
+                continue;
+            }
+            let taken = stock.take(code, needed);
+            if taken > 0 {
+                *plan.from_stock.entry(code.clone()).or_default() += taken;
+            }
+            let remaining = needed - taken;
+            if remaining == 0 {
+                continue;
+            }
+            match self.step(code, remaining)? {
+                Some(step) => {
+                    let craft = self.items[code].craft.as_ref().unwrap();
+                    for component in &craft.items {
+                        *demand.entry(component.code.clone()).or_default() +=
+                            component.quantity * step.crafts;
+                    }
+                    plan.steps.push(step);
+                }
+                None => {
+                    *plan.missing.entry(code.clone()).or_default() += remaining;
+                }
+            }
+        }
+
+        plan.steps.reverse();
+        Ok(plan)
+    }
+
+    /// Items reachable from `code`, ingredients before the items using them.
+    fn topological_order(&self, code: &ItemCode) -> Result<Vec<ItemCode>, CraftingError> {
+        fn visit(
+            planner: &CraftingPlanner,
+            code: &ItemCode,
+            visiting: &mut HashSet<ItemCode>,
+            order: &mut Vec<ItemCode>,
+        ) -> Result<(), CraftingError> {
+            if order.contains(code) {
+                return Ok(());
+            }
+            if !visiting.insert(code.clone()) {
+                return Err(CraftingError::Cycle(code.clone()));
+            }
+            if let Some(craft) = planner.items.get(code).and_then(|item| item.craft.as_ref()) {
+                for component in &craft.items {
+                    visit(planner, &component.code, visiting, order)?;
+                }
+            }
+            visiting.remove(code);
+            order.push(code.clone());
+            Ok(())
+        }
+
+        let mut order = Vec::new();
+        visit(self, code, &mut HashSet::new(), &mut order)?;
+        Ok(order)
+    }
+}
@@ -0,0 +1,766 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use thiserror::Error;
+
+use crate::{
+    action::CharacterAction,
+    api::schema::{
+        r#type::{
+            bank::{BankExpansion, BankGold},
+            character::{
+                Character, Cooldown, CooldownReason, InventoryInfo, InventorySlot, Position, Skill,
+            },
+            effect::Effect,
+            fight::{Fight, FightResult},
+            item::{Item, ItemCode, ItemComponent, ItemDetails, ItemSlot, Recycle},
+            map::{MapContent, MapContentType},
+        },
+        response::{
+            error_code,
+            my_characters::{
+                CharacterBankExpansionData, CharacterCraftData, CharacterEquipData,
+                CharacterFightData, CharacterGatherData, CharacterGoldTransactionData,
+                CharacterItemTransactionData, CharacterMovementData, CharacterRecycleData,
+                CharacterRestData, CharacterUseItemData,
+            },
+            ResponseData, ResponseError,
+        },
+    },
+    bank::BankState,
+    combat::{CombatSimulator, Fighter},
+    health::{rest_time, USE_ITEM_SECONDS},
+    inventory::InventoryChange,
+    leveling::DEFAULT_ACTION_SECONDS,
+    movement::travel_time,
+    runner::Executor,
+    world::World,
+};
+
+/// Cooldown of bank transactions and equipment changes.
+pub const TRANSACTION_SECONDS: u32 = 3;
+
+/// Why an action would be refused by the server.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DryRunError {
+    #[error("unknown character {0}")]
+    UnknownCharacter(String),
+    #[error("unknown item {0}")]
+    UnknownItem(ItemCode),
+    #[error("{0} cannot be crafted")]
+    NotCraftable(ItemCode),
+    #[error("no tile at ({}, {})", .0.x, .0.y)]
+    NoTile(Position),
+    #[error("already at ({}, {})", .0.x, .0.y)]
+    AlreadyThere(Position),
+    #[error("no {expected:?} at ({}, {})", .position.x, .position.y)]
+    WrongTile {
+        position: Position,
+        expected: MapContentType,
+    },
+    #[error("{skill} level {required} is required, the character is level {actual}")]
+    Level {
+        skill: Skill,
+        required: u32,
+        actual: u32,
+    },
+    #[error("{needed} {code} needed, {held} held")]
+    MissingItem {
+        code: ItemCode,
+        needed: u32,
+        held: u32,
+    },
+    #[error("{needed} {code} needed, {stored} in the bank")]
+    NotInBank {
+        code: ItemCode,
+        needed: u32,
+        stored: u32,
+    },
+    #[error("the inventory is full")]
+    InventoryFull,
+    #[error("the bank is full")]
+    BankFull,
+    #[error("{needed} gold needed, {held} held")]
+    NotEnoughGold { needed: u32, held: u32 },
+    #[error("{needed} gold needed, {stored} in the bank")]
+    NotEnoughBankGold { needed: u32, stored: u32 },
+    #[error("{code} cannot be equipped in the {slot:?} slot")]
+    WrongSlot { code: ItemCode, slot: ItemSlot },
+    #[error("the {0:?} slot is already used")]
+    SlotOccupied(ItemSlot),
+    #[error("the {0:?} slot is empty")]
+    SlotEmpty(ItemSlot),
+}
+
+impl DryRunError {
+    /// Error code the server answers with in the same situation.
+    pub fn code(&self) -> u16 {
+        match self {
+            DryRunError::UnknownCharacter(_) => error_code::CHARACTER_NOT_FOUND,
+            DryRunError::UnknownItem(_) | DryRunError::NotCraftable(_) | DryRunError::NoTile(_) => {
+                error_code::NOT_FOUND
+            }
+            DryRunError::AlreadyThere(_) => error_code::ALREADY_AT_DESTINATION,
+            DryRunError::WrongTile { .. } => error_code::CONTENT_NOT_FOUND,
+            DryRunError::Level {
+                skill: Skill::Fighting,
+                ..
+            } => error_code::LEVEL_TOO_LOW,
+            DryRunError::Level { .. } => error_code::SKILL_LEVEL_TOO_LOW,
+            DryRunError::MissingItem { .. } | DryRunError::NotInBank { .. } => {
+                error_code::MISSING_ITEM
+            }
+            DryRunError::InventoryFull => error_code::INVENTORY_FULL,
+            DryRunError::BankFull => error_code::BANK_FULL,
+            DryRunError::NotEnoughGold { .. } => error_code::INSUFFICIENT_GOLD,
+            DryRunError::NotEnoughBankGold { .. } => error_code::BANK_INSUFFICIENT_GOLD,
+            DryRunError::WrongSlot { .. } => error_code::INVALID_PAYLOAD,
+            DryRunError::SlotOccupied(_) => error_code::SLOT_NOT_EMPTY,
+            DryRunError::SlotEmpty(_) => error_code::SLOT_EMPTY,
+        }
+    }
+}
+
+impl From<DryRunError> for ResponseError {
+    fn from(error: DryRunError) -> Self {
+        ResponseError::new(error.code(), error.to_string())
+    }
+}
+
+/// The first step of a sequence that would fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidStep {
+    pub index: usize,
+    pub action: CharacterAction,
+    pub error: DryRunError,
+}
+
+/// Outcome of [`DryRun::validate`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DryRunReport {
+    /// Predicted cooldown of each valid step, in order.
+    pub cooldowns: Vec<Duration>,
+    /// The step the sequence stopped at, if any.
+    pub invalid: Option<InvalidStep>,
+}
+
+impl DryRunReport {
+    pub fn is_valid(&self) -> bool {
+        self.invalid.is_none()
+    }
+
+    /// Predicted time spent in cooldown over the valid steps.
+    pub fn total_cooldown(&self) -> Duration {
+        self.cooldowns.iter().sum()
+    }
+}
+
+/// Executor applying actions to a local model of the characters and the bank
+/// instead of sending them.
+///
+/// Each action is checked the way the server would: the tile, the levels,
+/// the items held or stored, the gold and the room left. A valid action
+/// changes the model and answers with a response built from it, an invalid
+/// one answers with the error code of the server.
+///
+/// Outcomes are deterministic: fights follow
+/// [`CombatSimulator::simulate_average`], and only drops with a rate of 1 are
+/// received, at their minimum quantity, while room is checked for the
+/// maximum. Recycling yields nothing, as none of its materials is certain.
+/// Responses carry no remaining cooldown so a runner never waits; the
+/// predicted cooldowns are added up per character instead.
+///
+/// Experience and gold are not modelled: responses report none and levels
+/// never rise. A sequence that needs a level gained along the way is
+/// reported invalid at the first step requiring it.
+pub struct DryRun {
+    world: Arc<World>,
+    characters: Mutex<HashMap<String, Model>>,
+    bank: Mutex<BankState>,
+}
+
+#[derive(Clone, Debug)]
+struct Model {
+    character: Character,
+    cooldown: Duration,
+}
+
+impl DryRun {
+    pub fn new(
+        world: Arc<World>,
+        characters: impl IntoIterator<Item = Character>,
+        bank: BankState,
+    ) -> Self {
+        let characters = characters
+            .into_iter()
+            .map(|character| {
+                let model = Model {
+                    character,
+                    cooldown: Duration::ZERO,
+                };
+                (model.character.name.clone(), model)
+            })
+            .collect();
+        Self {
+            world,
+            characters: Mutex::new(characters),
+            bank: Mutex::new(bank),
+        }
+    }
+
+    /// State of a character after the actions applied so far.
+    pub fn character(&self, name: &str) -> Option<Character> {
+        let characters = self.characters.lock().unwrap();
+        characters.get(name).map(|model| model.character.clone())
+    }
+
+    /// State of the bank after the actions applied so far.
+    pub fn bank(&self) -> BankState {
+        self.bank.lock().unwrap().clone()
+    }
+
+    /// Predicted cooldown of every action applied so far for a character.
+    pub fn cooldown(&self, name: &str) -> Duration {
+        let characters = self.characters.lock().unwrap();
+        characters
+            .get(name)
+            .map(|model| model.cooldown)
+            .unwrap_or_default()
+    }
+
+    /// Apply an action, leaving the model untouched when it is invalid.
+    pub fn apply(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> Result<(ResponseData, Duration), DryRunError> {
+        let mut characters = self.characters.lock().unwrap();
+        let model = characters
+            .get_mut(name)
+            .ok_or_else(|| DryRunError::UnknownCharacter(name.to_string()))?;
+        let mut bank = self.bank.lock().unwrap();
+
+        let mut character = model.character.clone();
+        let (data, cooldown) = self.step(&mut character, &bank, action)?;
+        bank.update(&data);
+        model.character = character;
+        model.cooldown += cooldown;
+        Ok((data, cooldown))
+    }
+
+    /// Apply a sequence of actions, stopping at the first invalid one.
+    pub fn validate(&self, name: &str, actions: &[CharacterAction]) -> DryRunReport {
+        let mut report = DryRunReport::default();
+        for (index, action) in actions.iter().enumerate() {
+            match self.apply(name, action) {
+                Ok((_, cooldown)) => report.cooldowns.push(cooldown),
+                Err(error) => {
+                    report.invalid = Some(InvalidStep {
+                        index,
+                        action: action.clone(),
+                        error,
+                    });
+                    break;
+                }
+            }
+        }
+        report
+    }
+
+    fn step(
+        &self,
+        character: &mut Character,
+        bank: &BankState,
+        action: &CharacterAction,
+    ) -> Result<(ResponseData, Duration), DryRunError> {
+        let world = &self.world;
+        let change = InventoryChange::predict(world, character, action);
+        let before = character.inventory_info.clone();
+        let response: (ResponseData, Duration) = match action {
+            CharacterAction::Move(movement) => {
+                let to = Position::new(movement.x, movement.y);
+                let destination = world.tile(&to).cloned().ok_or(DryRunError::NoTile(to))?;
+                if character.position == to {
+                    return Err(DryRunError::AlreadyThere(to));
+                }
+                let duration = travel_time(&character.position, &to, character.speed);
+                character.position = to;
+                let data = CharacterMovementData {
+                    cooldown: cooldown(duration, CooldownReason::Movement),
+                    destination,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Rest(_) => {
+                let stats = &mut character.combat_stats;
+                let duration = rest_time(stats.hp.max(0) as u32, stats.max_hp.max(0) as u32);
+                let hp_restored = (stats.max_hp - stats.hp).max(0) as u32;
+                stats.hp = stats.hp.max(stats.max_hp);
+                let data = CharacterRestData {
+                    cooldown: cooldown(duration, CooldownReason::Rest),
+                    hp_restored,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Fight(_) => {
+                let monster = tile(world, character, MapContentType::Monster)?
+                    .and_then(|content| content.monster())
+                    .and_then(|code| world.monster(&code))
+                    .ok_or_else(|| wrong_tile(character, MapContentType::Monster))?;
+                fits(&change, &before)?;
+                let fighter = Fighter::from_character(character, world.items());
+                let outcome = CombatSimulator::default()
+                    .simulate_average(&fighter, &Fighter::from_monster(monster));
+                let duration = fighter.cooldown(outcome.turns);
+                let drops: Vec<ItemComponent> = monster
+                    .drops
+                    .iter()
+                    .filter(|drop| outcome.won && drop.rate == 1 && drop.min_quantity > 0)
+                    .map(|drop| ItemComponent {
+                        code: drop.code.clone(),
+                        quantity: drop.min_quantity as u32,
+                    })
+                    .collect();
+                for drop in &drops {
+                    give(&mut character.inventory_info, &drop.code, drop.quantity);
+                }
+                // Boosts from utilities are not part of the character HP.
+                let boost = fighter.max_hp - character.combat_stats.max_hp;
+                character.combat_stats.hp = (outcome.hp - boost).max(0);
+                let data = CharacterFightData {
+                    cooldown: cooldown(duration, CooldownReason::Fight),
+                    fight: Fight {
+                        xp: 0,
+                        gold: 0,
+                        drops,
+                        turns: outcome.turns as i32,
+                        monster_blocked_hits: Default::default(),
+                        player_blocked_hits: Default::default(),
+                        logs: Vec::new(),
+                        result: if outcome.won {
+                            FightResult::Win
+                        } else {
+                            FightResult::Lose
+                        },
+                    },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Gather(_) => {
+                let resource = tile(world, character, MapContentType::Resource)?
+                    .and_then(|content| content.resource())
+                    .and_then(|code| world.resource(&code))
+                    .ok_or_else(|| wrong_tile(character, MapContentType::Resource))?;
+                level(character, resource.skill, resource.level.max(0) as u32)?;
+                fits(&change, &before)?;
+                let items: Vec<ItemComponent> = resource
+                    .drops
+                    .iter()
+                    .filter(|drop| drop.rate == 1 && drop.min_quantity > 0)
+                    .map(|drop| ItemComponent {
+                        code: drop.code.clone(),
+                        quantity: drop.min_quantity,
+                    })
+                    .collect();
+                for item in &items {
+                    give(&mut character.inventory_info, &item.code, item.quantity);
+                }
+                let duration = seconds(DEFAULT_ACTION_SECONDS);
+                let data = CharacterGatherData {
+                    cooldown: cooldown(duration, CooldownReason::Gathering),
+                    details: ItemDetails { xp: 0, items },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Craft(craft) => {
+                let recipe = item(world, &craft.code)?
+                    .craft
+                    .as_ref()
+                    .ok_or_else(|| DryRunError::NotCraftable(craft.code.clone()))?;
+                workshop(world, character, recipe.skill)?;
+                level(character, recipe.skill, recipe.level)?;
+                for component in &recipe.items {
+                    let quantity = component.quantity * craft.quantity;
+                    take(&mut character.inventory_info, &component.code, quantity)?;
+                }
+                fits(&change, &before)?;
+                let quantity = recipe.quantity * craft.quantity;
+                give(&mut character.inventory_info, &craft.code, quantity);
+                let duration = seconds(DEFAULT_ACTION_SECONDS * craft.quantity);
+                let data = CharacterCraftData {
+                    cooldown: cooldown(duration, CooldownReason::Crafting),
+                    details: ItemDetails {
+                        xp: 0,
+                        items: vec![ItemComponent {
+                            code: craft.code.clone(),
+                            quantity,
+                        }],
+                    },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Recycle(recycle) => {
+                let recipe = item(world, &recycle.code)?
+                    .craft
+                    .as_ref()
+                    .ok_or_else(|| DryRunError::NotCraftable(recycle.code.clone()))?;
+                workshop(world, character, recipe.skill)?;
+                level(character, recipe.skill, recipe.level)?;
+                take(
+                    &mut character.inventory_info,
+                    &recycle.code,
+                    recycle.quantity,
+                )?;
+                fits(&change, &before)?;
+                // The server returns a random part of the materials, possibly
+                // none of some, so no item is guaranteed.
+                let items = Vec::new();
+                let duration = seconds(DEFAULT_ACTION_SECONDS * recycle.quantity);
+                let data = CharacterRecycleData {
+                    cooldown: cooldown(duration, CooldownReason::Recycling),
+                    details: Recycle { items },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Equip(equip) => {
+                let item = item(world, &equip.code)?.clone();
+                if equip.slot.to_item_type().as_ref() != Some(&item.r#type) {
+                    return Err(DryRunError::WrongSlot {
+                        code: equip.code.clone(),
+                        slot: equip.slot,
+                    });
+                }
+                take(&mut character.inventory_info, &equip.code, equip.quantity)?;
+                level(character, Skill::Fighting, item.level)?;
+                if character.equipment.get(&equip.slot).is_some() {
+                    return Err(DryRunError::SlotOccupied(equip.slot));
+                }
+                character
+                    .equipment
+                    .set(&equip.slot, equip.code.clone(), equip.quantity);
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterEquipData {
+                    cooldown: cooldown(duration, CooldownReason::Equip),
+                    slot: equip.slot,
+                    item,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Unequip(unequip) => {
+                let code = character
+                    .equipment
+                    .get(&unequip.slot)
+                    .cloned()
+                    .ok_or(DryRunError::SlotEmpty(unequip.slot))?;
+                let item = item(world, &code)?.clone();
+                fits(&change, &before)?;
+                let held = character.equipment.quantity(&unequip.slot);
+                if unequip.quantity >= held {
+                    character.equipment.clear(&unequip.slot);
+                } else {
+                    character
+                        .equipment
+                        .set(&unequip.slot, code.clone(), held - unequip.quantity);
+                }
+                give(
+                    &mut character.inventory_info,
+                    &code,
+                    unequip.quantity.min(held),
+                );
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterEquipData {
+                    cooldown: cooldown(duration, CooldownReason::Unequip),
+                    slot: unequip.slot,
+                    item,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::UseItem(use_item) => {
+                let item = item(world, &use_item.code)?.clone();
+                take(
+                    &mut character.inventory_info,
+                    &use_item.code,
+                    use_item.quantity,
+                )?;
+                level(character, Skill::Fighting, item.level)?;
+                let heal: i32 = item
+                    .effects
+                    .iter()
+                    .filter_map(|effect| match effect {
+                        Effect::Heal(heal) => Some(*heal),
+                        _ => None,
+                    })
+                    .sum();
+                let stats = &mut character.combat_stats;
+                let healed = stats.hp + heal * use_item.quantity as i32;
+                stats.hp = healed.min(stats.max_hp).max(stats.hp);
+                let duration = seconds(USE_ITEM_SECONDS);
+                let data = CharacterUseItemData {
+                    cooldown: cooldown(duration, CooldownReason::Use),
+                    item,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Deposit(deposit) => {
+                tile(world, character, MapContentType::Bank)?;
+                let item = item(world, &deposit.code)?.clone();
+                take(
+                    &mut character.inventory_info,
+                    &deposit.code,
+                    deposit.quantity,
+                )?;
+                if bank.quantity(&deposit.code) == 0 && bank.free_slots() == 0 {
+                    return Err(DryRunError::BankFull);
+                }
+                let mut stored: Vec<ItemComponent> = bank.to_components();
+                match stored.iter_mut().find(|stored| stored.code == deposit.code) {
+                    Some(stored) => stored.quantity += deposit.quantity,
+                    None => stored.push(ItemComponent {
+                        code: deposit.code.clone(),
+                        quantity: deposit.quantity,
+                    }),
+                }
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterItemTransactionData {
+                    cooldown: cooldown(duration, CooldownReason::Deposit),
+                    item,
+                    bank: stored,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::Withdraw(withdraw) => {
+                tile(world, character, MapContentType::Bank)?;
+                let item = item(world, &withdraw.code)?.clone();
+                let stored = bank.quantity(&withdraw.code);
+                if stored < withdraw.quantity {
+                    return Err(DryRunError::NotInBank {
+                        code: withdraw.code.clone(),
+                        needed: withdraw.quantity,
+                        stored,
+                    });
+                }
+                fits(&change, &before)?;
+                give(
+                    &mut character.inventory_info,
+                    &withdraw.code,
+                    withdraw.quantity,
+                );
+                let mut stored: Vec<ItemComponent> = bank.to_components();
+                for stored in stored
+                    .iter_mut()
+                    .filter(|stored| stored.code == withdraw.code)
+                {
+                    stored.quantity -= withdraw.quantity;
+                }
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterItemTransactionData {
+                    cooldown: cooldown(duration, CooldownReason::Withdraw),
+                    item,
+                    bank: stored,
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::DepositGold(deposit) => {
+                tile(world, character, MapContentType::Bank)?;
+                spend(character, deposit.quantity)?;
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterGoldTransactionData {
+                    cooldown: cooldown(duration, CooldownReason::DepositGold),
+                    bank: BankGold {
+                        quantity: bank.gold() + deposit.quantity,
+                    },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::WithdrawGold(withdraw) => {
+                tile(world, character, MapContentType::Bank)?;
+                if bank.gold() < withdraw.quantity {
+                    return Err(DryRunError::NotEnoughBankGold {
+                        needed: withdraw.quantity,
+                        stored: bank.gold(),
+                    });
+                }
+                character.combat_stats.gold += withdraw.quantity as i32;
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterGoldTransactionData {
+                    cooldown: cooldown(duration, CooldownReason::WithdrawGold),
+                    bank: BankGold {
+                        quantity: bank.gold() - withdraw.quantity,
+                    },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+            CharacterAction::BuyBankExpansion(_) => {
+                tile(world, character, MapContentType::Bank)?;
+                let price = bank.next_expansion_cost();
+                spend(character, price)?;
+                let duration = seconds(TRANSACTION_SECONDS);
+                let data = CharacterBankExpansionData {
+                    cooldown: cooldown(duration, CooldownReason::BuyBankExpansion),
+                    transaction: BankExpansion { price },
+                    character: character.clone(),
+                };
+                (data.into(), duration)
+            }
+        };
+        Ok(response)
+    }
+}
+
+impl Executor for DryRun {
+    async fn execute(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        Ok(self.apply(name, action)?.0)
+    }
+}
+
+fn seconds(seconds: u32) -> Duration {
+    Duration::from_secs(u64::from(seconds))
+}
+
+/// A cooldown already over, lasting `duration` in total.
+fn cooldown(duration: Duration, reason: CooldownReason) -> Cooldown {
+    Cooldown {
+        total_seconds: duration.as_secs_f64().ceil() as i32,
+        remaining_seconds: 0,
+        started_at: Default::default(),
+        expiration: Default::default(),
+        reason,
+    }
+}
+
+fn wrong_tile(character: &Character, expected: MapContentType) -> DryRunError {
+    DryRunError::WrongTile {
+        position: character.position,
+        expected,
+    }
+}
+
+/// Content of the tile the character stands on, when of the expected type.
+fn tile<'w>(
+    world: &'w World,
+    character: &Character,
+    expected: MapContentType,
+) -> Result<Option<&'w MapContent>, DryRunError> {
+    world
+        .tile(&character.position)
+        .and_then(|tile| tile.content.as_ref())
+        .filter(|content| content.r#type == expected)
+        .map(Some)
+        .ok_or_else(|| wrong_tile(character, expected))
+}
+
+/// Check the character stands in the workshop of `skill`.
+fn workshop(world: &World, character: &Character, skill: Skill) -> Result<(), DryRunError> {
+    tile(world, character, MapContentType::Workshop)?
+        .filter(|content| content.code.as_str() == skill.to_string())
+        .map(|_| ())
+        .ok_or_else(|| wrong_tile(character, MapContentType::Workshop))
+}
+
+fn item<'w>(world: &'w World, code: &ItemCode) -> Result<&'w Item, DryRunError> {
+    world
+        .item(code)
+        .ok_or_else(|| DryRunError::UnknownItem(code.clone()))
+}
+
+fn level(character: &Character, skill: Skill, required: u32) -> Result<(), DryRunError> {
+    let actual = character.skill(skill).level;
+    if actual < required {
+        return Err(DryRunError::Level {
+            skill,
+            required,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn fits(change: &InventoryChange, inventory: &InventoryInfo) -> Result<(), DryRunError> {
+    if change.fits(inventory) {
+        Ok(())
+    } else {
+        Err(DryRunError::InventoryFull)
+    }
+}
+
+fn spend(character: &mut Character, gold: u32) -> Result<(), DryRunError> {
+    let held = character.combat_stats.gold.max(0) as u32;
+    if held < gold {
+        return Err(DryRunError::NotEnoughGold { needed: gold, held });
+    }
+    character.combat_stats.gold -= gold as i32;
+    Ok(())
+}
+
+/// Remove items from the inventory, emptying the slots left with none.
+fn take(inventory: &mut InventoryInfo, code: &ItemCode, quantity: u32) -> Result<(), DryRunError> {
+    let held = inventory.quantity(code);
+    if held < quantity {
+        return Err(DryRunError::MissingItem {
+            code: code.clone(),
+            needed: quantity,
+            held,
+        });
+    }
+    let mut left = quantity;
+    for slot in inventory.inventory.iter_mut().flatten() {
+        if slot.code != code.as_str() || left == 0 {
+            continue;
+        }
+        let taken = left.min(slot.quantity);
+        slot.quantity -= taken;
+        left -= taken;
+        if slot.quantity == 0 {
+            slot.code.clear();
+        }
+    }
+    Ok(())
+}
+
+/// Add items to the slot already holding them, or to the first empty one.
+fn give(inventory: &mut InventoryInfo, code: &ItemCode, quantity: u32) {
+    if quantity == 0 {
+        return;
+    }
+    let slots = inventory.inventory.get_or_insert_with(Vec::new);
+    if let Some(slot) = slots.iter_mut().find(|slot| slot.code == code.as_str()) {
+        slot.quantity += quantity;
+        return;
+    }
+    let empty = slots
+        .iter_mut()
+        .find(|slot| slot.quantity == 0 || slot.code.is_empty());
+    match empty {
+        Some(slot) => {
+            slot.code = code.to_string();
+            slot.quantity = quantity;
+        }
+        None => {
+            let slot = slots.len() as i32 + 1;
+            slots.push(InventorySlot {
+                slot,
+                code: code.to_string(),
+                quantity,
+            });
+        }
+    }
+}
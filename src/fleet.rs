@@ -0,0 +1,426 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{interval_at, sleep_until, Instant, MissedTickBehavior},
+};
+
+use crate::{
+    action::CharacterAction,
+    api::{
+        schema::{
+            r#type::{
+                character::Character,
+                item::{ItemCode, ItemComponent},
+                time::ServerClock,
+            },
+            response::{
+                error_code, my_account::AccountBankDetailsData, ResponseData, ResponseError,
+            },
+        },
+        Api,
+    },
+    bank::{BankState, Drift},
+    persistence::Snapshot,
+    runner::{remaining_cooldown, ActionResult, CharacterRunner, Executor, RunnerHandle},
+};
+
+/// At most `requests` requests in any window of `per`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, per: Duration) -> Self {
+        Self { requests, per }
+    }
+}
+
+/// Sliding window over the start times of recent requests.
+struct RateLimiter {
+    limit: RateLimit,
+    sent: tokio::sync::Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            sent: tokio::sync::Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Wait until a request may be sent, and count it.
+    async fn acquire(&self) {
+        let mut sent = self.sent.lock().await;
+        loop {
+            let now = Instant::now();
+            while sent
+                .front()
+                .is_some_and(|start| *start + self.limit.per <= now)
+            {
+                sent.pop_front();
+            }
+            if sent.len() < self.limit.requests.max(1) as usize {
+                sent.push_back(now);
+                return;
+            }
+            sleep_until(sent[0] + self.limit.per).await;
+        }
+    }
+}
+
+/// Last known bank content and the quantities held by withdrawals in flight.
+struct BankStock {
+    state: BankState,
+    reserved: HashMap<ItemCode, u32>,
+}
+
+impl BankStock {
+    fn available(&self, code: &ItemCode) -> u32 {
+        let stored = self.state.quantity(code);
+        stored.saturating_sub(self.reserved.get(code).copied().unwrap_or_default())
+    }
+}
+
+/// Executor shared by every runner of a [`Fleet`]: rate limits requests and
+/// keeps withdrawals within what the bank holds.
+pub struct FleetExecutor<E> {
+    inner: E,
+    limiter: RateLimiter,
+    bank: Mutex<BankStock>,
+}
+
+impl<E: Executor> FleetExecutor<E> {
+    pub fn new(inner: E, limit: RateLimit, bank: BankState) -> Self {
+        Self::with_limiter(inner, RateLimiter::new(limit), bank)
+    }
+
+    fn with_limiter(inner: E, limiter: RateLimiter, bank: BankState) -> Self {
+        let stock = BankStock {
+            state: bank,
+            reserved: HashMap::new(),
+        };
+        Self {
+            inner,
+            limiter,
+            bank: Mutex::new(stock),
+        }
+    }
+
+    /// Quantity of an item in the bank that no withdrawal has claimed yet.
+    pub fn bank_quantity(&self, code: &ItemCode) -> u32 {
+        self.bank.lock().unwrap().available(code)
+    }
+
+    /// Current state of the bank mirror.
+    pub fn bank(&self) -> BankState {
+        self.bank.lock().unwrap().state.clone()
+    }
+
+    /// Claim items for a withdrawal, or fail like the API would.
+    fn reserve(&self, code: &ItemCode, quantity: u32) -> Result<(), ResponseError> {
+        let mut bank = self.bank.lock().unwrap();
+        if bank.available(code) < quantity {
+            return Err(ResponseError::new(
+                error_code::MISSING_ITEM,
+                format!("Missing item or insufficient quantity in bank: {code}."),
+            ));
+        }
+        *bank.reserved.entry(code.clone()).or_default() += quantity;
+        Ok(())
+    }
+
+    /// Record the result of an action, releasing the items claimed by a
+    /// withdrawal once the bank content reflects it.
+    fn settle(
+        &self,
+        withdrawal: Option<(&ItemCode, u32)>,
+        result: &Result<ResponseData, ResponseError>,
+    ) {
+        let mut bank = self.bank.lock().unwrap();
+        if let Ok(data) = result {
+            bank.state.update(data);
+        }
+        if let Some((code, quantity)) = withdrawal {
+            if let Some(reserved) = bank.reserved.get_mut(code) {
+                *reserved = reserved.saturating_sub(quantity);
+            }
+        }
+    }
+}
+
+impl<E: Executor> Executor for FleetExecutor<E> {
+    async fn execute(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        let withdrawal = match action {
+            CharacterAction::Withdraw(withdraw) => {
+                self.reserve(&withdraw.code, withdraw.quantity)?;
+                Some((&withdraw.code, withdraw.quantity))
+            }
+            _ => None,
+        };
+
+        self.limiter.acquire().await;
+        let result = self.inner.execute(name, action).await;
+        self.settle(withdrawal, &result);
+        result
+    }
+
+    fn before(&self, name: &str, action: &CharacterAction) -> Vec<CharacterAction> {
+        self.inner.before(name, action)
+    }
+}
+
+impl FleetExecutor<Api> {
+    /// Fetch the bank and reconcile the mirror with it. Withdrawals in flight
+    /// keep their claim on the items.
+    async fn sync_bank(&self) -> Result<Vec<Drift>, ResponseError> {
+        let (details, items) = fetch_bank(&self.inner, &self.limiter).await?;
+        let mut bank = self.bank.lock().unwrap();
+        Ok(bank.state.reconcile(&details, &items))
+    }
+}
+
+/// A fleet restarted from a [`Snapshot`].
+pub struct Resumed<E = Api> {
+    pub fleet: Fleet<E>,
+    /// Differences found between the saved bank and the server.
+    pub drift: Vec<Drift>,
+    /// Characters whose queue could not be reconciled and was left empty;
+    /// their goal should be planned again.
+    pub replan: Vec<String>,
+}
+
+/// Fetch the bank details and content, each request counted by `limiter`.
+async fn fetch_bank(
+    api: &Api,
+    limiter: &RateLimiter,
+) -> Result<(AccountBankDetailsData, Vec<ItemComponent>), ResponseError> {
+    limiter.acquire().await;
+    let details = api.bank_details().await?;
+    limiter.acquire().await;
+    let items = api.bank_items().await?;
+    Ok((details, items))
+}
+
+/// Estimate the server clock from its status, the request counted by
+/// `limiter`. Without the `chrono` feature the server time cannot be read,
+/// and the local clock is used.
+async fn fetch_clock(api: &Api, limiter: &RateLimiter) -> Result<ServerClock, ResponseError> {
+    #[cfg(feature = "chrono")]
+    {
+        limiter.acquire().await;
+        let status = api.status().await?;
+        Ok(status.clock(chrono::Utc::now()).unwrap_or_default())
+    }
+    #[cfg(not(feature = "chrono"))]
+    {
+        let _ = (api, limiter);
+        Ok(ServerClock::default())
+    }
+}
+
+/// Runs every character of an account, one [`CharacterRunner`] each, over a
+/// single rate-limited executor. Requests made by the fleet itself, to
+/// bootstrap, resume or sync the bank, count against the same limit.
+pub struct Fleet<E = Api> {
+    executor: Arc<FleetExecutor<E>>,
+    handles: BTreeMap<String, RunnerHandle>,
+    tasks: Vec<JoinHandle<()>>,
+    events: mpsc::UnboundedReceiver<ActionResult>,
+    sync: Option<JoinHandle<()>>,
+}
+
+impl Fleet<Api> {
+    /// Fetch the characters and bank of the account and start a runner for
+    /// each character, after the cooldown the server reports on it.
+    pub async fn bootstrap(api: Api, limit: RateLimit) -> Result<Self, ResponseError> {
+        let limiter = RateLimiter::new(limit);
+        let clock = fetch_clock(&api, &limiter).await?;
+        limiter.acquire().await;
+        let characters = api.my_characters().await?;
+        let (details, items) = fetch_bank(&api, &limiter).await?;
+        let bank = BankState::new(&details, &items);
+        Ok(Self::start(api, limiter, clock, characters, bank))
+    }
+
+    /// Resume from a snapshot saved by a previous run.
+    ///
+    /// Each saved character is fetched again, so that its queue restarts from
+    /// where it actually is and after its current cooldown (see
+    /// [`Snapshot::queue`]), and the saved bank is reconciled with the server.
+    pub async fn resume(
+        api: Api,
+        limit: RateLimit,
+        snapshot: &Snapshot,
+    ) -> Result<Resumed, ResponseError> {
+        let limiter = RateLimiter::new(limit);
+        let clock = fetch_clock(&api, &limiter).await?;
+        let mut characters = Vec::new();
+        for name in snapshot.characters.keys() {
+            limiter.acquire().await;
+            characters.push(api.character(name).await?);
+        }
+        let (details, items) = fetch_bank(&api, &limiter).await?;
+        let (bank, drift) = match &snapshot.bank {
+            Some(saved) => {
+                let mut bank = saved.clone();
+                let drift = bank.reconcile(&details, &items);
+                (bank, drift)
+            }
+            None => (BankState::new(&details, &items), Vec::new()),
+        };
+
+        let queues: Vec<_> = characters
+            .iter()
+            .map(|character| (character.name.clone(), snapshot.queue(character)))
+            .collect();
+        let fleet = Self::start(api, limiter, clock, characters, bank);
+        let mut replan = Vec::new();
+        for (name, queue) in queues {
+            match (queue, fleet.handle(&name)) {
+                (Some(queue), Some(handle)) => handle.extend(queue),
+                (Some(_), None) => {}
+                (None, _) => replan.push(name),
+            }
+        }
+        Ok(Resumed {
+            fleet,
+            drift,
+            replan,
+        })
+    }
+
+    /// Reconcile the bank mirror with the server, returning the drift found.
+    ///
+    /// Withdrawals in flight keep their claim on the items. See
+    /// [`sync_bank_every`](Self::sync_bank_every) to do it periodically.
+    pub async fn sync_bank(&self) -> Result<Vec<Drift>, ResponseError> {
+        self.executor.sync_bank().await
+    }
+
+    /// Reconcile the bank mirror with the server every `every`, in a task
+    /// that replaces the one started by a previous call and stops with the
+    /// fleet.
+    ///
+    /// The drift found by each sync, or the error it failed with, is sent on
+    /// the returned channel; a failed sync is tried again at the next tick.
+    /// The requests count against the fleet rate limit.
+    pub fn sync_bank_every(
+        &mut self,
+        every: Duration,
+    ) -> mpsc::UnboundedReceiver<Result<Vec<Drift>, ResponseError>> {
+        let executor = self.executor.clone();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            // The bank was just fetched, or synced by the previous task.
+            let mut ticks = interval_at(Instant::now() + every, every);
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                // Nobody listening is no reason to let the mirror go stale.
+                let _ = sender.send(executor.sync_bank().await);
+            }
+        });
+        if let Some(previous) = self.sync.replace(task) {
+            previous.abort();
+        }
+        receiver
+    }
+}
+
+impl<E: Executor + 'static> Fleet<E> {
+    /// Start a runner for each character, waiting for the cooldown left on it
+    /// before its first action, as measured by the local clock. Must be called
+    /// within a tokio runtime.
+    pub fn new(executor: E, limit: RateLimit, characters: Vec<Character>, bank: BankState) -> Self {
+        let limiter = RateLimiter::new(limit);
+        Self::start(executor, limiter, ServerClock::default(), characters, bank)
+    }
+
+    /// Start a runner for each character, waiting for the cooldown left on it,
+    /// as measured by `clock`, before its first action.
+    fn start(
+        executor: E,
+        limiter: RateLimiter,
+        clock: ServerClock,
+        characters: Vec<Character>,
+        bank: BankState,
+    ) -> Self {
+        let executor = Arc::new(FleetExecutor::with_limiter(executor, limiter, bank));
+        let (sender, events) = mpsc::unbounded_channel();
+        let mut handles = BTreeMap::new();
+        let mut tasks = Vec::new();
+        for character in characters {
+            let name = character.name.clone();
+            let (runner, handle) =
+                CharacterRunner::with_sender(name.clone(), executor.clone(), sender.clone());
+            let runner = runner
+                .with_cooldown(remaining_cooldown(&character, &clock))
+                .with_character(character);
+            tasks.push(tokio::spawn(runner.run()));
+            handles.insert(name, handle);
+        }
+        Self {
+            executor,
+            handles,
+            tasks,
+            events,
+            sync: None,
+        }
+    }
+
+    /// Names of the characters, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.handles.keys().map(String::as_str)
+    }
+
+    pub fn handle(&self, name: &str) -> Option<&RunnerHandle> {
+        self.handles.get(name)
+    }
+
+    /// Latest known state of every character.
+    pub fn characters(&self) -> Vec<Character> {
+        self.handles
+            .values()
+            .filter_map(RunnerHandle::character)
+            .collect()
+    }
+
+    /// Quantity of an item in the bank that no withdrawal has claimed yet.
+    pub fn bank_quantity(&self, code: &ItemCode) -> u32 {
+        self.executor.bank_quantity(code)
+    }
+
+    /// Current state of the bank mirror.
+    pub fn bank(&self) -> BankState {
+        self.executor.bank()
+    }
+
+    /// Next result from any character.
+    pub async fn next_event(&mut self) -> Option<ActionResult> {
+        self.events.recv().await
+    }
+
+    /// Cancel every runner and wait for them to stop. The bank sync task, if
+    /// any, is stopped.
+    pub async fn shutdown(self) {
+        if let Some(sync) = &self.sync {
+            sync.abort();
+        }
+        self.handles.values().for_each(RunnerHandle::cancel);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    action::{CharacterAction, Equip, Unequip},
+    api::schema::r#type::{
+        character::{Character, Equipment, Skill},
+        effect::StatBlock,
+        item::{Item, ItemCode, ItemComponent, ItemSlot},
+        monster::Monster,
+    },
+    combat::{CombatSimulator, Fighter},
+};
+
+/// Slots considered by the optimizer. Utilities are consumables and are left
+/// as they are.
+const GEAR_SLOTS: [ItemSlot; 12] = [
+    ItemSlot::Weapon,
+    ItemSlot::Shield,
+    ItemSlot::Helmet,
+    ItemSlot::BodyArmor,
+    ItemSlot::LegArmor,
+    ItemSlot::Boots,
+    ItemSlot::Ring1,
+    ItemSlot::Ring2,
+    ItemSlot::Amulet,
+    ItemSlot::Artifact1,
+    ItemSlot::Artifact2,
+    ItemSlot::Artifact3,
+];
+
+/// Upper bound on improvement passes over every slot.
+const MAX_PASSES: usize = 4;
+
+/// What a loadout is optimized for.
+#[derive(Clone, Copy, Debug)]
+pub enum Objective<'a> {
+    /// Highest win rate against a monster, then shortest and least
+    /// damaging fights.
+    Fight(&'a Monster),
+    /// Lowest gathering cooldown for a skill, then most prospecting.
+    Gathering(Skill),
+}
+
+/// The best loadout found and how to reach it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GearPlan {
+    /// Equipment once the plan has been carried out.
+    pub equipment: Equipment,
+    /// Items to withdraw from the bank before equipping.
+    pub withdraw: Vec<ItemComponent>,
+    /// [`Equip`] and [`Unequip`] actions in order, every unequip coming
+    /// before the equips.
+    pub actions: Vec<CharacterAction>,
+}
+
+impl GearPlan {
+    /// Whether the current equipment is already the best one.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// Picks the best gear from what a character and the bank hold.
+#[derive(Clone, Copy, Debug)]
+pub struct GearOptimizer<'a> {
+    items: &'a HashMap<ItemCode, Item>,
+    simulator: CombatSimulator,
+}
+
+impl<'a> GearOptimizer<'a> {
+    pub fn new(items: &'a HashMap<ItemCode, Item>) -> Self {
+        Self {
+            items,
+            simulator: CombatSimulator::default(),
+        }
+    }
+
+    /// Use a different simulator to score fights, e.g. with fewer runs.
+    pub fn with_simulator(mut self, simulator: CombatSimulator) -> Self {
+        self.simulator = simulator;
+        self
+    }
+
+    /// Find the best loadout for `objective` among the items equipped, in
+    /// the inventory and in `bank`, skipping items above the character level.
+    ///
+    /// The search improves one slot at a time until no single change helps,
+    /// so a slot is only changed when it strictly improves the score.
+    /// Fights are scored with the character at full HP.
+    pub fn optimize(
+        &self,
+        character: &Character,
+        bank: &[ItemComponent],
+        objective: Objective,
+    ) -> GearPlan {
+        let mut gear = character.equipment.clone();
+        gear.clear(&ItemSlot::Utility1);
+        gear.clear(&ItemSlot::Utility2);
+
+        let mut held: HashMap<ItemCode, u32> = HashMap::new();
+        for (code, quantity) in character.inventory_info.items() {
+            *held.entry(code).or_default() += quantity;
+        }
+        for (_, code) in gear.iter() {
+            *held.entry(code.clone()).or_default() += 1;
+        }
+        let mut available = held.clone();
+        for item in bank {
+            *available.entry(item.code.clone()).or_default() += item.quantity;
+        }
+
+        let mut base = Fighter::from_character(character, self.items);
+        apply(&mut base, &StatBlock::from_equipment(&gear, self.items), -1);
+        let score = |gear: &Equipment| self.score(&base, gear, objective);
+
+        let level = character.combat_stats.level.max(0) as u32;
+        let mut best = score(&gear);
+        for _ in 0..MAX_PASSES {
+            let mut improved = false;
+            for slot in GEAR_SLOTS {
+                for item in self.candidates(slot, level) {
+                    if gear.get(&slot) == Some(&item.code)
+                        || used(&gear, &item.code)
+                            >= available.get(&item.code).copied().unwrap_or_default()
+                    {
+                        continue;
+                    }
+                    let mut trial = gear.clone();
+                    trial.set(&slot, item.code.clone(), 1);
+                    let trial_score = score(&trial);
+                    if trial_score.better_than(&best) {
+                        (gear, best) = (trial, trial_score);
+                        improved = true;
+                    }
+                }
+                if gear.get(&slot).is_some() {
+                    let mut trial = gear.clone();
+                    trial.clear(&slot);
+                    let trial_score = score(&trial);
+                    if trial_score.better_than(&best) {
+                        (gear, best) = (trial, trial_score);
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        let mut withdraw: Vec<_> = available
+            .keys()
+            .filter_map(|code| {
+                let missing =
+                    used(&gear, code).saturating_sub(held.get(code).copied().unwrap_or_default());
+                (missing > 0).then(|| ItemComponent {
+                    code: code.clone(),
+                    quantity: missing,
+                })
+            })
+            .collect();
+        withdraw.sort_by(|a, b| a.code.cmp(&b.code));
+
+        for slot in [ItemSlot::Utility1, ItemSlot::Utility2] {
+            if let Some(code) = character.equipment.get(&slot) {
+                gear.set(&slot, code.clone(), character.equipment.quantity(&slot));
+            }
+        }
+
+        let changes = character.equipment.diff(&gear);
+        let unequips = changes
+            .iter()
+            .filter(|change| change.before.is_some())
+            .map(|change| Unequip::new(change.slot).into());
+        let equips = changes.iter().filter_map(|change| {
+            change
+                .after
+                .clone()
+                .map(|code| Equip::new(code, change.slot).into())
+        });
+
+        GearPlan {
+            actions: unequips.chain(equips).collect(),
+            equipment: gear,
+            withdraw,
+        }
+    }
+
+    /// Items that fit `slot` and can be worn at `level`, sorted by code.
+    fn candidates(&self, slot: ItemSlot, level: u32) -> Vec<&'a Item> {
+        let Some(r#type) = slot.to_item_type() else {
+            return Vec::new();
+        };
+        let mut candidates: Vec<_> = self
+            .items
+            .values()
+            .filter(|item| item.r#type == r#type && item.level <= level)
+            .collect();
+        candidates.sort_by(|a, b| a.code.cmp(&b.code));
+        candidates
+    }
+
+    fn score(&self, base: &Fighter, gear: &Equipment, objective: Objective) -> Score {
+        let stats = StatBlock::from_equipment(gear, self.items);
+        match objective {
+            Objective::Fight(monster) => {
+                let mut character = base.clone();
+                apply(&mut character, &stats, 1);
+                character.hp = character.max_hp;
+                let monster = Fighter::from_monster(monster);
+                let prediction = self.simulator.simulate(&character, &monster);
+                Score([
+                    prediction.win_probability,
+                    -prediction.expected_turns,
+                    -prediction.expected_hp_lost,
+                    character.expected_damage(&monster),
+                ])
+            }
+            Objective::Gathering(skill) => Score([
+                -f64::from(stats.gathering.get(&skill).copied().unwrap_or_default()),
+                f64::from(stats.prospecting),
+                0.0,
+                0.0,
+            ]),
+        }
+    }
+}
+
+/// Number of slots holding `code`.
+fn used(gear: &Equipment, code: &ItemCode) -> u32 {
+    gear.iter()
+        .filter(|(_, equipped)| *equipped == code)
+        .count() as u32
+}
+
+/// Add (`sign` = 1) or remove (`sign` = -1) item stats from a fighter.
+fn apply(fighter: &mut Fighter, stats: &StatBlock, sign: i32) {
+    fighter.hp += sign * stats.hp;
+    fighter.max_hp += sign * stats.hp;
+    fighter.attack += stats.attack * sign;
+    fighter.dmg += stats.dmg * sign;
+    fighter.res += stats.res * sign;
+    fighter.critical_strike += sign * stats.critical_strike;
+    fighter.haste += sign * stats.haste;
+}
+
+/// Criteria compared in order, higher is better.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Score([f64; 4]);
+
+impl Score {
+    fn better_than(&self, other: &Score) -> bool {
+        self.0
+            .iter()
+            .zip(other.0)
+            .map(|(a, b)| {
+                if (a - b).abs() < 1e-9 {
+                    Ordering::Equal
+                } else {
+                    a.total_cmp(&b)
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            == Some(Ordering::Greater)
+    }
+}
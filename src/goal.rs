@@ -0,0 +1,408 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    action::{CharacterAction, Craft, Deposit, Equip, Fight, Gather, Move, Unequip, Withdraw},
+    api::schema::{
+        r#type::{
+            character::{Character, Position, Skill},
+            code::{InvalidCode, MapCode},
+            item::{ItemCode, ItemComponent},
+            map::{MapContent, MapContentType},
+        },
+        response::{error_code, ResponseError},
+    },
+    crafting::{CraftingError, CraftingPlanner, Stock},
+    leveling::{Activity, LevelingError, LevelingPlanner},
+    sourcing::{Source, Sourcing},
+    world::World,
+};
+
+/// Errors after which planning again from the current state is enough.
+const REPLANNABLE: [u16; 4] = [
+    error_code::MISSING_ITEM,
+    error_code::ALREADY_AT_DESTINATION,
+    error_code::COOLDOWN,
+    error_code::CONTENT_NOT_FOUND,
+];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PlanError {
+    #[error("unknown item {0}")]
+    UnknownItem(ItemCode),
+    #[error("{0} cannot be equipped")]
+    NotEquipment(ItemCode),
+    #[error("no known way to obtain {0}")]
+    Unobtainable(ItemCode),
+    #[error("{skill} level {level} is required")]
+    Level { skill: Skill, level: u32 },
+    #[error("no {0:?} tile on the map")]
+    NoTile(MapContentType),
+    #[error("action failed with code {code}: {message}")]
+    Failed { code: u16, message: String },
+    #[error(transparent)]
+    Crafting(#[from] CraftingError),
+    #[error(transparent)]
+    Leveling(#[from] LevelingError),
+    #[error(transparent)]
+    Code(#[from] InvalidCode),
+}
+
+/// A state a character should reach.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "goal", rename_all = "snake_case")]
+pub enum Goal {
+    /// Hold at least `quantity` of an item in the bank.
+    BankItem { code: ItemCode, quantity: u32 },
+    /// Reach a level in a skill.
+    Level { skill: Skill, level: u32 },
+    /// Have an item equipped.
+    Equip { code: ItemCode },
+}
+
+/// Actions expected to reach a goal.
+///
+/// Gathering and fighting are planned for their expected drops, so a goal
+/// may need another plan once the actions have been carried out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoalPlan {
+    pub goal: Goal,
+    pub actions: Vec<CharacterAction>,
+}
+
+impl GoalPlan {
+    /// Whether the goal is already reached.
+    pub fn is_done(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// Turns goals into sequences of typed actions.
+#[derive(Clone, Debug)]
+pub struct GoalPlanner<'a> {
+    world: &'a World,
+    sourcing: Sourcing,
+    leveling: LevelingPlanner<'a>,
+}
+
+impl<'a> GoalPlanner<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self {
+            world,
+            sourcing: Sourcing::new(world),
+            leveling: LevelingPlanner::new(world),
+        }
+    }
+
+    /// Plan level goals with a different planner, e.g. one that recorded
+    /// actual XP.
+    pub fn with_leveling(mut self, leveling: LevelingPlanner<'a>) -> Self {
+        self.leveling = leveling;
+        self
+    }
+
+    /// Plan the actions bringing `character` to `goal`, with `bank` the
+    /// current content of the bank.
+    pub fn plan(
+        &self,
+        goal: &Goal,
+        character: &Character,
+        bank: &[ItemComponent],
+    ) -> Result<GoalPlan, PlanError> {
+        let mut draft = Draft::new(self, character, bank);
+        draft.reach(goal)?;
+        Ok(GoalPlan {
+            goal: goal.clone(),
+            actions: draft.actions,
+        })
+    }
+
+    /// Plan again after an action of a previous plan failed with `error`,
+    /// from the state of the character and bank after the failure.
+    ///
+    /// A full inventory is emptied into the bank first. Errors that are not
+    /// caused by an outdated plan are returned as [`PlanError::Failed`].
+    pub fn replan(
+        &self,
+        goal: &Goal,
+        character: &Character,
+        bank: &[ItemComponent],
+        error: &ResponseError,
+    ) -> Result<GoalPlan, PlanError> {
+        let mut draft = Draft::new(self, character, bank);
+        match error.error.code {
+            error_code::INVENTORY_FULL => draft.deposit_all()?,
+            code if REPLANNABLE.contains(&code) => {}
+            code => {
+                return Err(PlanError::Failed {
+                    code,
+                    message: error.error.message.clone(),
+                })
+            }
+        }
+        draft.reach(goal)?;
+        Ok(GoalPlan {
+            goal: goal.clone(),
+            actions: draft.actions,
+        })
+    }
+}
+
+/// A plan being written, with where the character and its items will be.
+struct Draft<'p, 'a> {
+    planner: &'p GoalPlanner<'a>,
+    character: &'p Character,
+    position: Position,
+    inventory: Stock,
+    bank: Stock,
+    actions: Vec<CharacterAction>,
+}
+
+impl<'p, 'a> Draft<'p, 'a> {
+    fn new(planner: &'p GoalPlanner<'a>, character: &'p Character, bank: &[ItemComponent]) -> Self {
+        Self {
+            planner,
+            character,
+            position: character.position,
+            inventory: Stock::new().with_inventory(&character.inventory_info),
+            bank: Stock::new().with_bank(bank),
+            actions: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, action: impl Into<CharacterAction>) {
+        self.actions.push(action.into());
+    }
+
+    fn reach(&mut self, goal: &Goal) -> Result<(), PlanError> {
+        match goal {
+            Goal::BankItem { code, quantity } => self.bank_item(code, *quantity),
+            Goal::Level { skill, level } => self.level(*skill, *level),
+            Goal::Equip { code } => self.equip(code),
+        }
+    }
+
+    fn bank_item(&mut self, code: &ItemCode, quantity: u32) -> Result<(), PlanError> {
+        // What is already in the bank stays there.
+        let stored = self.bank.take(code, quantity);
+        let needed = quantity - stored;
+        if needed > 0 {
+            self.obtain(code, needed)?;
+            self.go_to_bank()?;
+            self.inventory.take(code, needed);
+            self.push(Deposit::new(code.clone(), needed));
+        }
+        self.bank.add(code.clone(), quantity);
+        Ok(())
+    }
+
+    fn level(&mut self, skill: Skill, level: u32) -> Result<(), PlanError> {
+        let plan = self.planner.leveling.plan(self.character, skill, level)?;
+        for step in plan.steps {
+            match step.activity {
+                Activity::Gather(resource) => {
+                    self.go_to(MapContentType::Resource, resource.into())?;
+                    self.repeat(Gather, step.actions);
+                }
+                Activity::Fight(monster) => {
+                    self.go_to(MapContentType::Monster, monster.into())?;
+                    self.repeat(Fight, step.actions);
+                }
+                Activity::Craft(code) => {
+                    let craft = self
+                        .planner
+                        .world
+                        .item(&code)
+                        .and_then(|item| item.craft.as_ref())
+                        .ok_or_else(|| PlanError::UnknownItem(code.clone()))?;
+                    for component in &craft.items {
+                        self.obtain(&component.code, component.quantity * step.actions)?;
+                    }
+                    self.craft(&code, step.actions)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn equip(&mut self, code: &ItemCode) -> Result<(), PlanError> {
+        let equipment = &self.character.equipment;
+        if equipment.iter().any(|(_, equipped)| equipped == code) {
+            return Ok(());
+        }
+        let item = self
+            .planner
+            .world
+            .item(code)
+            .ok_or_else(|| PlanError::UnknownItem(code.clone()))?;
+        let slot = equipment
+            .find_available_slot(&item.r#type)
+            .or_else(|| item.r#type.possible_slots().first().copied())
+            .ok_or_else(|| PlanError::NotEquipment(code.clone()))?;
+        if self.character.skill(Skill::Fighting).level < item.level {
+            return Err(PlanError::Level {
+                skill: Skill::Fighting,
+                level: item.level,
+            });
+        }
+
+        self.obtain(code, 1)?;
+        if !equipment.is_slot_empty(&slot) {
+            self.push(Unequip::new(slot));
+        }
+        self.inventory.take(code, 1);
+        self.push(Equip::new(code.clone(), slot));
+        Ok(())
+    }
+
+    /// Get `quantity` of an item into the inventory, from the inventory and
+    /// the bank first, then by crafting, gathering or fighting.
+    fn obtain(&mut self, code: &ItemCode, quantity: u32) -> Result<(), PlanError> {
+        let mut stock = self.bank.clone();
+        for (code, held) in self.inventory.iter() {
+            stock.add(code.clone(), held);
+        }
+        let target = ItemComponent {
+            code: code.clone(),
+            quantity,
+        };
+        let plan = CraftingPlanner::new(self.planner.world.items()).plan(&target, &stock)?;
+        if let Some((skill, level)) = plan
+            .required_levels()
+            .into_iter()
+            .find(|(skill, level)| self.character.skill(*skill).level < *level)
+        {
+            return Err(PlanError::Level { skill, level });
+        }
+
+        let withdrawals: Vec<_> = plan
+            .from_stock
+            .iter()
+            .filter_map(|(code, taken)| {
+                let missing = taken.saturating_sub(self.inventory.quantity(code));
+                (missing > 0).then(|| (code.clone(), missing))
+            })
+            .collect();
+        if !withdrawals.is_empty() {
+            self.go_to_bank()?;
+            for (code, quantity) in withdrawals {
+                self.bank.take(&code, quantity);
+                self.inventory.add(code.clone(), quantity);
+                self.push(Withdraw::new(code, quantity));
+            }
+        }
+        for (code, quantity) in &plan.missing {
+            self.farm(code, *quantity)?;
+        }
+        for step in &plan.steps {
+            self.craft(&step.code, step.crafts)?;
+        }
+        Ok(())
+    }
+
+    /// Gather or fight for a raw material, with the cheapest source the
+    /// character can use.
+    fn farm(&mut self, code: &ItemCode, quantity: u32) -> Result<(), PlanError> {
+        let ranked = self.planner.sourcing.rank(code, self.character);
+        let mut farmable = ranked.iter().filter_map(|ranked| {
+            let (r#type, content, action) = match &ranked.source {
+                Source::Resource { resource, .. } => (
+                    MapContentType::Resource,
+                    MapCode::from(resource.clone()),
+                    CharacterAction::from(Gather),
+                ),
+                Source::Monster { monster, .. } => (
+                    MapContentType::Monster,
+                    monster.clone().into(),
+                    Fight.into(),
+                ),
+                Source::Craft { .. } => return None,
+            };
+            Some((ranked, r#type, content, action))
+        });
+        let Some((best, r#type, content, action)) = farmable.next() else {
+            return Err(PlanError::Unobtainable(code.clone()));
+        };
+        if !best.available {
+            let (skill, level) = best.source.requirement();
+            return Err(PlanError::Level { skill, level });
+        }
+
+        let actions = (f64::from(quantity) * best.actions_per_unit).ceil() as u32;
+        self.go_to(r#type, content)?;
+        self.repeat(action, actions);
+        self.inventory.add(code.clone(), quantity);
+        Ok(())
+    }
+
+    /// Craft from materials already in the inventory.
+    fn craft(&mut self, code: &ItemCode, crafts: u32) -> Result<(), PlanError> {
+        let craft = self
+            .planner
+            .world
+            .item(code)
+            .and_then(|item| item.craft.as_ref())
+            .ok_or_else(|| PlanError::UnknownItem(code.clone()))?;
+        let workshop = craft.skill.to_string().parse::<MapCode>()?;
+        self.go_to(MapContentType::Workshop, workshop)?;
+        for component in &craft.items {
+            self.inventory
+                .take(&component.code, component.quantity * crafts);
+        }
+        self.inventory.add(code.clone(), craft.quantity * crafts);
+        self.push(Craft::new(code.clone(), crafts));
+        Ok(())
+    }
+
+    /// Deposit the whole inventory.
+    fn deposit_all(&mut self) -> Result<(), PlanError> {
+        let held: Vec<_> = self
+            .inventory
+            .iter()
+            .map(|(code, quantity)| (code.clone(), quantity))
+            .collect();
+        if held.is_empty() {
+            return Ok(());
+        }
+        self.go_to_bank()?;
+        for (code, quantity) in held {
+            self.inventory.take(&code, quantity);
+            self.bank.add(code.clone(), quantity);
+            self.push(Deposit::new(code, quantity));
+        }
+        Ok(())
+    }
+
+    fn repeat(&mut self, action: impl Into<CharacterAction>, times: u32) {
+        let action = action.into();
+        self.actions
+            .extend(std::iter::repeat_n(action, times as usize));
+    }
+
+    fn go_to_bank(&mut self) -> Result<(), PlanError> {
+        let bank = self
+            .planner
+            .world
+            .nearest_of_type(&self.position, MapContentType::Bank)
+            .ok_or(PlanError::NoTile(MapContentType::Bank))?;
+        self.move_to(bank.position());
+        Ok(())
+    }
+
+    fn go_to(&mut self, r#type: MapContentType, code: MapCode) -> Result<(), PlanError> {
+        let tile = self
+            .planner
+            .world
+            .nearest(&self.position, &MapContent::new(r#type, code))
+            .ok_or(PlanError::NoTile(r#type))?;
+        self.move_to(tile.position());
+        Ok(())
+    }
+
+    fn move_to(&mut self, position: Position) {
+        if position != self.position {
+            self.push(Move::new(position.x, position.y));
+            self.position = position;
+        }
+    }
+}
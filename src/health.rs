@@ -0,0 +1,211 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::{
+    action::{CharacterAction, Rest, UseItem},
+    api::schema::r#type::{
+        character::Character,
+        effect::Effect,
+        item::{Item, ItemCode, ItemComponent},
+        monster::Monster,
+    },
+    combat::{CombatSimulator, Fighter},
+    leveling::MIN_WIN_PROBABILITY,
+};
+
+/// HP recovered per second of rest.
+pub const REST_HP_PER_SECOND: u32 = 5;
+
+/// Shortest rest cooldown, even at full HP.
+pub const MIN_REST_SECONDS: u32 = 3;
+
+/// Cooldown of one use item call, whatever the quantity used.
+pub const USE_ITEM_SECONDS: u32 = 3;
+
+/// Cooldown of a rest from `hp` back to `max_hp`.
+pub fn rest_time(hp: u32, max_hp: u32) -> Duration {
+    let seconds = max_hp.saturating_sub(hp).div_ceil(REST_HP_PER_SECOND);
+    Duration::from_secs(u64::from(seconds.max(MIN_REST_SECONDS)))
+}
+
+/// How to get enough HP for a fight.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    /// The character can fight right away.
+    Ready,
+    /// Rest back to full HP.
+    Rest { duration: Duration },
+    /// Eat food from the inventory, one use item call per item.
+    Eat {
+        items: Vec<ItemComponent>,
+        heal: u32,
+        duration: Duration,
+    },
+    /// The fight is too risky even at full HP.
+    Unwinnable,
+}
+
+impl Recovery {
+    /// Downtime before the fight.
+    pub fn duration(&self) -> Duration {
+        match self {
+            Recovery::Rest { duration } | Recovery::Eat { duration, .. } => *duration,
+            Recovery::Ready | Recovery::Unwinnable => Duration::ZERO,
+        }
+    }
+
+    /// Actions to perform before the fight.
+    pub fn actions(&self) -> Vec<CharacterAction> {
+        match self {
+            Recovery::Rest { .. } => vec![Rest.into()],
+            Recovery::Eat { items, .. } => items
+                .iter()
+                .map(|item| UseItem::new(item.code.clone(), item.quantity).into())
+                .collect(),
+            Recovery::Ready | Recovery::Unwinnable => Vec::new(),
+        }
+    }
+}
+
+/// Decides between resting and eating before each fight.
+///
+/// The HP needed is the lowest starting HP with which the simulator
+/// predicts the fight is won often enough. Below it, the character either
+/// rests or eats, whichever has the shortest cooldown; on a tie it rests to
+/// keep the food.
+#[derive(Clone, Copy, Debug)]
+pub struct HpPolicy<'a> {
+    items: &'a HashMap<ItemCode, Item>,
+    simulator: CombatSimulator,
+    min_win_probability: f64,
+}
+
+impl<'a> HpPolicy<'a> {
+    pub fn new(items: &'a HashMap<ItemCode, Item>) -> Self {
+        Self {
+            items,
+            simulator: CombatSimulator::default(),
+            min_win_probability: MIN_WIN_PROBABILITY,
+        }
+    }
+
+    /// Use a different simulator to predict fights, e.g. with fewer runs.
+    pub fn with_simulator(mut self, simulator: CombatSimulator) -> Self {
+        self.simulator = simulator;
+        self
+    }
+
+    /// Win rate a fight must reach, [`MIN_WIN_PROBABILITY`] by default.
+    pub fn with_min_win_probability(mut self, probability: f64) -> Self {
+        self.min_win_probability = probability;
+        self
+    }
+
+    /// Lowest HP the character should start the fight with, `None` when even
+    /// full HP is not enough.
+    pub fn required_hp(&self, character: &Character, monster: &Monster) -> Option<u32> {
+        let base = Fighter::from_character(character, self.items);
+        let monster = Fighter::from_monster(monster);
+        // Boosts from utilities add to the HP of the character.
+        let boost = base.max_hp - character.combat_stats.max_hp;
+        let wins = |hp: u32| {
+            let mut fighter = base.clone();
+            fighter.hp = hp as i32 + boost;
+            self.simulator.simulate(&fighter, &monster).win_probability >= self.min_win_probability
+        };
+
+        let max_hp = character.combat_stats.max_hp.max(1) as u32;
+        if !wins(max_hp) {
+            return None;
+        }
+        let (mut low, mut high) = (1, max_hp);
+        while low < high {
+            let middle = (low + high) / 2;
+            if wins(middle) {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        Some(low)
+    }
+
+    /// How `character` should recover before fighting `monster`.
+    pub fn before_fight(&self, character: &Character, monster: &Monster) -> Recovery {
+        let Some(required) = self.required_hp(character, monster) else {
+            return Recovery::Unwinnable;
+        };
+        let hp = character.combat_stats.hp.max(0) as u32;
+        if hp >= required {
+            return Recovery::Ready;
+        }
+
+        let rest = rest_time(hp, character.combat_stats.max_hp.max(0) as u32);
+        match self.meal(character, required - hp) {
+            Some((items, heal)) => {
+                let duration =
+                    Duration::from_secs(u64::from(USE_ITEM_SECONDS) * items.len() as u64);
+                if duration < rest {
+                    Recovery::Eat {
+                        items,
+                        heal,
+                        duration,
+                    }
+                } else {
+                    Recovery::Rest { duration: rest }
+                }
+            }
+            None => Recovery::Rest { duration: rest },
+        }
+    }
+
+    /// Food from the inventory healing at least `missing` HP, in as few use
+    /// item calls as possible and then with the least healing wasted.
+    fn meal(&self, character: &Character, missing: u32) -> Option<(Vec<ItemComponent>, u32)> {
+        let level = character.combat_stats.level.max(0) as u32;
+        let mut foods: Vec<(ItemCode, u32, u32)> = character
+            .inventory_info
+            .items()
+            .filter_map(|(code, quantity)| {
+                let item = self.items.get(&code).filter(|item| item.level <= level)?;
+                let heal = item.effects.iter().find_map(|effect| match effect {
+                    Effect::Heal(heal) if *heal > 0 => Some(*heal as u32),
+                    _ => None,
+                })?;
+                Some((code, heal, quantity))
+            })
+            .collect();
+
+        // A single food, when one is enough.
+        let single = foods
+            .iter()
+            .filter_map(|(code, heal, quantity)| {
+                let needed = missing.div_ceil(*heal);
+                (needed <= *quantity).then(|| (code, needed, needed * heal))
+            })
+            .min_by(|a, b| a.2.cmp(&b.2).then(a.0.cmp(b.0)));
+        if let Some((code, quantity, heal)) = single {
+            let item = ItemComponent {
+                code: code.clone(),
+                quantity,
+            };
+            return Some((vec![item], heal));
+        }
+
+        // Otherwise the biggest heals first.
+        foods.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut items = Vec::new();
+        let mut healed = 0;
+        for (code, heal, quantity) in foods {
+            if healed >= missing {
+                break;
+            }
+            let used = (missing - healed).div_ceil(heal).min(quantity);
+            healed += used * heal;
+            items.push(ItemComponent {
+                code,
+                quantity: used,
+            });
+        }
+        (healed >= missing).then_some((items, healed))
+    }
+}
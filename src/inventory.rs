@@ -0,0 +1,240 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    action::{ActionOutcome, CharacterAction, Deposit, Move},
+    api::schema::{
+        r#type::{
+            character::{Character, InventoryInfo},
+            item::ItemCode,
+            map::MapContentType,
+        },
+        response::{ResponseData, ResponseError},
+    },
+    runner::Executor,
+    world::World,
+};
+
+/// Items an action may add to and remove from the inventory.
+///
+/// Drops are counted at their maximum quantity, however rare they are, so
+/// that an action predicted to fit never fills the inventory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InventoryChange {
+    pub added: BTreeMap<ItemCode, u32>,
+    pub removed: BTreeMap<ItemCode, u32>,
+}
+
+impl InventoryChange {
+    /// Predict the change caused by `action` for `character`, using the
+    /// content of the tile the character stands on for gathering and
+    /// fighting.
+    pub fn predict(world: &World, character: &Character, action: &CharacterAction) -> Self {
+        let mut change = Self::default();
+        let content = world
+            .tile(&character.position)
+            .and_then(|tile| tile.content.as_ref());
+        match action {
+            CharacterAction::Gather(_) => {
+                let resource = content
+                    .and_then(|content| content.resource())
+                    .and_then(|code| world.resource(&code));
+                for drop in resource.iter().flat_map(|resource| &resource.drops) {
+                    change.add(&drop.code, drop.max_quantity);
+                }
+            }
+            CharacterAction::Fight(_) => {
+                let monster = content
+                    .and_then(|content| content.monster())
+                    .and_then(|code| world.monster(&code));
+                for drop in monster.iter().flat_map(|monster| &monster.drops) {
+                    change.add(&drop.code, drop.max_quantity.max(0) as u32);
+                }
+            }
+            CharacterAction::Craft(craft) => {
+                if let Some(recipe) = world.item(&craft.code).and_then(|item| item.craft.as_ref()) {
+                    for component in &recipe.items {
+                        change.remove(&component.code, component.quantity * craft.quantity);
+                    }
+                    change.add(&craft.code, recipe.quantity * craft.quantity);
+                }
+            }
+            CharacterAction::Recycle(recycle) => {
+                change.remove(&recycle.code, recycle.quantity);
+                if let Some(recipe) = world
+                    .item(&recycle.code)
+                    .and_then(|item| item.craft.as_ref())
+                {
+                    for component in &recipe.items {
+                        change.add(&component.code, component.quantity * recycle.quantity);
+                    }
+                }
+            }
+            CharacterAction::Unequip(unequip) => {
+                if let Some(code) = character.equipment.get(&unequip.slot) {
+                    change.add(code, unequip.quantity);
+                }
+            }
+            CharacterAction::Withdraw(withdraw) => change.add(&withdraw.code, withdraw.quantity),
+            CharacterAction::Equip(equip) => change.remove(&equip.code, equip.quantity),
+            CharacterAction::UseItem(item) => change.remove(&item.code, item.quantity),
+            CharacterAction::Deposit(deposit) => change.remove(&deposit.code, deposit.quantity),
+            CharacterAction::Move(_)
+            | CharacterAction::Rest(_)
+            | CharacterAction::DepositGold(_)
+            | CharacterAction::WithdrawGold(_)
+            | CharacterAction::BuyBankExpansion(_) => {}
+        }
+        change
+    }
+
+    fn add(&mut self, code: &ItemCode, quantity: u32) {
+        *self.added.entry(code.clone()).or_default() += quantity;
+    }
+
+    fn remove(&mut self, code: &ItemCode, quantity: u32) {
+        *self.removed.entry(code.clone()).or_default() += quantity;
+    }
+
+    /// Whether the inventory has room for the change, in items and in slots.
+    pub fn fits(&self, inventory: &InventoryInfo) -> bool {
+        let added: u32 = self.added.values().sum();
+        let removed: u32 = self
+            .removed
+            .iter()
+            .map(|(code, quantity)| (*quantity).min(inventory.quantity(code)))
+            .sum();
+        if added > inventory.free_capacity() + removed {
+            return false;
+        }
+
+        let freed = self
+            .removed
+            .iter()
+            .filter(|(code, quantity)| {
+                let held = inventory.quantity(code);
+                held > 0 && **quantity >= held
+            })
+            .count();
+        let new = self
+            .added
+            .keys()
+            .filter(|code| inventory.quantity(code) == 0)
+            .count();
+        new <= inventory.free_slots() + freed
+    }
+}
+
+/// Inserts a trip to the nearest bank before an action that could overflow
+/// the inventory.
+#[derive(Clone, Copy, Debug)]
+pub struct BankTripPolicy<'a> {
+    world: &'a World,
+}
+
+impl<'a> BankTripPolicy<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self { world }
+    }
+
+    /// Actions to perform before `action`: none when it fits, otherwise a
+    /// move to the nearest bank, a deposit of everything the action does not
+    /// consume, and a move back.
+    pub fn before(&self, character: &Character, action: &CharacterAction) -> Vec<CharacterAction> {
+        let change = InventoryChange::predict(self.world, character, action);
+        if change.fits(&character.inventory_info) {
+            return Vec::new();
+        }
+
+        let mut held: BTreeMap<ItemCode, u32> = BTreeMap::new();
+        for (code, quantity) in character.inventory_info.items() {
+            *held.entry(code).or_default() += quantity;
+        }
+        let mut deposits: Vec<CharacterAction> = held
+            .into_iter()
+            .filter_map(|(code, quantity)| {
+                let kept = change.removed.get(&code).copied().unwrap_or_default();
+                let deposited = quantity.saturating_sub(kept);
+                (deposited > 0).then(|| Deposit::new(code, deposited).into())
+            })
+            .collect();
+        let bank = self
+            .world
+            .nearest_of_type(&character.position, MapContentType::Bank);
+        let Some(bank) = bank.filter(|_| !deposits.is_empty()) else {
+            return Vec::new();
+        };
+
+        let from = character.position;
+        let to = bank.position();
+        if from == to {
+            return deposits;
+        }
+        let mut actions = vec![Move::new(to.x, to.y).into()];
+        actions.append(&mut deposits);
+        actions.push(Move::new(from.x, from.y).into());
+        actions
+    }
+}
+
+/// Executor applying a [`BankTripPolicy`] before every action.
+///
+/// The character state is learnt from responses, or given with
+/// [`BankTrips::observe`]; actions of unknown characters are passed through.
+/// The trip is returned by [`Executor::before`], so a runner queues it ahead
+/// of the action like any other actions.
+pub struct BankTrips<E> {
+    inner: E,
+    world: Arc<World>,
+    characters: Mutex<HashMap<String, Character>>,
+}
+
+impl<E: Executor> BankTrips<E> {
+    pub fn new(inner: E, world: Arc<World>) -> Self {
+        Self {
+            inner,
+            world,
+            characters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Record the latest state of a character.
+    pub fn observe(&self, character: &Character) {
+        self.characters
+            .lock()
+            .unwrap()
+            .insert(character.name.clone(), character.clone());
+    }
+}
+
+impl<E: Executor> Executor for BankTrips<E> {
+    async fn execute(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        let result = self.inner.execute(name, action).await;
+        if let Ok(data) = &result {
+            self.observe(data.character());
+        }
+        result
+    }
+
+    fn before(&self, name: &str, action: &CharacterAction) -> Vec<CharacterAction> {
+        let characters = self.characters.lock().unwrap();
+        let mut trip = match characters.get(name) {
+            Some(character) => BankTripPolicy::new(&self.world).before(character, action),
+            None => Vec::new(),
+        };
+        if trip.is_empty() {
+            trip = self.inner.before(name, action);
+        }
+        trip
+    }
+}
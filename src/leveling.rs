@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+use thiserror::Error;
+
+use crate::{
+    api::schema::{
+        r#type::{
+            character::{Character, Skill},
+            code::{MonsterCode, ResourceCode},
+            item::ItemCode,
+        },
+        response::my_characters::{CharacterCraftData, CharacterFightData, CharacterGatherData},
+    },
+    combat::{CombatSimulator, Fighter},
+    sourcing::Sourcing,
+    world::World,
+};
+
+/// Cooldown assumed for gathering and crafting until one has been measured.
+pub const DEFAULT_ACTION_SECONDS: u32 = 25;
+
+/// Monsters are only fought when the simulator predicts at least this win
+/// rate.
+pub const MIN_WIN_PROBABILITY: f64 = 0.9;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LevelingError {
+    #[error("nothing gives {skill} XP at level {level}")]
+    NoActivity { skill: Skill, level: u32 },
+    #[error("XP needed for {skill} level {level} is unknown")]
+    UnknownXp { skill: Skill, level: u32 },
+}
+
+/// Something a character repeats to earn XP.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Activity {
+    Gather(ResourceCode),
+    Craft(ItemCode),
+    Fight(MonsterCode),
+}
+
+/// XP and cooldown measured for an activity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Observed {
+    pub actions: u32,
+    pub xp: u64,
+    pub seconds: u64,
+}
+
+impl Observed {
+    pub fn xp_per_action(&self) -> f64 {
+        self.xp as f64 / f64::from(self.actions.max(1))
+    }
+
+    pub fn seconds_per_action(&self) -> f64 {
+        self.seconds as f64 / f64::from(self.actions.max(1))
+    }
+}
+
+/// Levels spent on one activity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelStep {
+    /// Level the step starts at.
+    pub level: u32,
+    pub activity: Activity,
+    /// Actions earning XP.
+    pub actions: u32,
+    /// Gather or fight actions needed to obtain craft materials.
+    pub material_actions: u32,
+    pub xp_per_action: f64,
+    pub duration: Duration,
+    /// Whether the XP needed or the XP per action is a guess, to be refined
+    /// once observed.
+    pub estimated: bool,
+}
+
+/// How to reach a level in a skill.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelingPlan {
+    pub skill: Skill,
+    /// One step per level, from the current level up.
+    pub steps: Vec<LevelStep>,
+    /// Total actions, including material actions.
+    pub actions: u32,
+    pub duration: Duration,
+}
+
+/// Best activity found for a level.
+struct Candidate {
+    activity: Activity,
+    xp_per_action: f64,
+    /// Seconds per XP action, including material actions.
+    seconds_per_action: f64,
+    material_actions: f64,
+    /// Whether the activity has never been measured.
+    estimated: bool,
+}
+
+/// Projects skill progress from the catalogue and from measured XP.
+///
+/// The XP needed by each level is the `max_xp` the server reports for it,
+/// learnt from the characters [`observe`](Self::observe)d. Levels above the
+/// highest one observed are assumed to need as much XP as it.
+///
+/// Until a response has been recorded for an activity, its XP and cooldown
+/// are rough estimates, and an activity the skill has outgrown is only
+/// dropped once a response shows it gives no XP. Record responses as they
+/// arrive and plan again to refine the projection.
+#[derive(Clone, Debug)]
+pub struct LevelingPlanner<'a> {
+    world: &'a World,
+    sourcing: Sourcing,
+    simulator: CombatSimulator,
+    observed: HashMap<Activity, Observed>,
+    curve: HashMap<Skill, BTreeMap<u32, u32>>,
+}
+
+impl<'a> LevelingPlanner<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self {
+            world,
+            sourcing: Sourcing::new(world),
+            simulator: CombatSimulator::default(),
+            observed: HashMap::new(),
+            curve: HashMap::new(),
+        }
+    }
+
+    /// Use a different simulator to predict fights, e.g. with fewer runs.
+    pub fn with_simulator(mut self, simulator: CombatSimulator) -> Self {
+        self.simulator = simulator;
+        self
+    }
+
+    /// Record `actions` actions of an activity that gave `xp` in total and
+    /// took `seconds` of cooldown.
+    pub fn record(&mut self, activity: Activity, actions: u32, xp: u32, seconds: u32) {
+        let observed = self.observed.entry(activity).or_default();
+        observed.actions += actions;
+        observed.xp += u64::from(xp);
+        observed.seconds += u64::from(seconds);
+    }
+
+    pub fn record_gather(&mut self, resource: ResourceCode, data: &CharacterGatherData) {
+        self.record_response(
+            Activity::Gather(resource),
+            1,
+            data.details.xp,
+            data.cooldown.total_seconds,
+        );
+        self.observe(&data.character);
+    }
+
+    /// Record a craft of `quantity` items.
+    pub fn record_craft(&mut self, item: ItemCode, quantity: u32, data: &CharacterCraftData) {
+        self.record_response(
+            Activity::Craft(item),
+            quantity,
+            data.details.xp,
+            data.cooldown.total_seconds,
+        );
+        self.observe(&data.character);
+    }
+
+    pub fn record_fight(&mut self, monster: MonsterCode, data: &CharacterFightData) {
+        self.record_response(
+            Activity::Fight(monster),
+            1,
+            data.fight.xp,
+            data.cooldown.total_seconds,
+        );
+        self.observe(&data.character);
+    }
+
+    fn record_response(&mut self, activity: Activity, actions: u32, xp: i32, seconds: i32) {
+        self.record(activity, actions, xp.max(0) as u32, seconds.max(0) as u32);
+    }
+
+    /// Measurements recorded for an activity.
+    pub fn observed(&self, activity: &Activity) -> Option<&Observed> {
+        self.observed.get(activity)
+    }
+
+    /// Learn the XP needed by the current level of every skill.
+    pub fn observe(&mut self, character: &Character) {
+        for (skill, progress) in character.skills() {
+            if progress.level > 0 && progress.max_xp > 0 {
+                self.curve
+                    .entry(skill)
+                    .or_default()
+                    .insert(progress.level, progress.max_xp);
+            }
+        }
+    }
+
+    /// XP needed to go from `level` to the next one, if a character has been
+    /// observed at that level.
+    pub fn xp_to_next(&self, skill: Skill, level: u32) -> Option<u32> {
+        self.curve.get(&skill)?.get(&level).copied()
+    }
+
+    /// XP needed to go from `level` to the next one, and whether it was
+    /// taken from a lower level. `current` is the level of the character
+    /// being planned for and `max_xp` the XP it needs.
+    fn xp_needed(
+        &self,
+        skill: Skill,
+        level: u32,
+        current: u32,
+        max_xp: u32,
+    ) -> Result<(u32, bool), LevelingError> {
+        if level == current && max_xp > 0 {
+            return Ok((max_xp, false));
+        }
+        if let Some(xp) = self.xp_to_next(skill, level) {
+            return Ok((xp, false));
+        }
+        let observed = self
+            .curve
+            .get(&skill)
+            .and_then(|curve| curve.range(..level).next_back())
+            .map(|(known, xp)| (*known, *xp));
+        let own = (max_xp > 0).then_some((current, max_xp));
+        observed
+            .into_iter()
+            .chain(own)
+            .max()
+            .map(|(_, xp)| (xp, true))
+            .ok_or(LevelingError::UnknownXp { skill, level })
+    }
+
+    /// Plan how to bring `skill` to `target` level, picking at each level the
+    /// activity giving the most XP per second.
+    pub fn plan(
+        &self,
+        character: &Character,
+        skill: Skill,
+        target: u32,
+    ) -> Result<LevelingPlan, LevelingError> {
+        let progress = character.skill(skill);
+        let mut plan = LevelingPlan {
+            skill,
+            steps: Vec::new(),
+            actions: 0,
+            duration: Duration::ZERO,
+        };
+
+        let mut xp = f64::from(progress.xp);
+        for level in progress.level..target {
+            let (needed, guessed) =
+                self.xp_needed(skill, level, progress.level, progress.max_xp)?;
+            let needed = f64::from(needed);
+            let best = self
+                .candidates(character, skill, level)
+                .into_iter()
+                .max_by(|a, b| {
+                    (a.xp_per_action / a.seconds_per_action)
+                        .total_cmp(&(b.xp_per_action / b.seconds_per_action))
+                        .then(b.activity.cmp(&a.activity))
+                })
+                .ok_or(LevelingError::NoActivity { skill, level })?;
+
+            let actions = ((needed - xp).max(0.0) / best.xp_per_action).ceil() as u32;
+            xp += f64::from(actions) * best.xp_per_action - needed;
+            let material_actions = (best.material_actions * f64::from(actions)).ceil() as u32;
+            let duration = Duration::from_secs_f64(best.seconds_per_action * f64::from(actions));
+
+            plan.actions += actions + material_actions;
+            plan.duration += duration;
+            plan.steps.push(LevelStep {
+                level,
+                activity: best.activity,
+                actions,
+                material_actions,
+                xp_per_action: best.xp_per_action,
+                duration,
+                estimated: guessed || best.estimated,
+            });
+        }
+        Ok(plan)
+    }
+
+    /// Activities giving `skill` XP at `level`.
+    fn candidates(&self, character: &Character, skill: Skill, level: u32) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        let default_seconds = f64::from(DEFAULT_ACTION_SECONDS);
+
+        for resource in self.world.resources().values() {
+            let required = resource.level.max(0) as u32;
+            if resource.skill == skill && required <= level {
+                let activity = Activity::Gather(resource.code.clone());
+                candidates.extend(self.candidate(activity, required, default_seconds, 0.0));
+            }
+        }
+
+        for item in self.world.items().values() {
+            let Some(craft) = item.craft.as_ref().filter(|craft| craft.skill == skill) else {
+                continue;
+            };
+            if craft.level > level {
+                continue;
+            }
+            let materials: f64 = craft
+                .items
+                .iter()
+                .map(|component| {
+                    f64::from(component.quantity)
+                        * self
+                            .sourcing
+                            .best_actions_per_unit(&component.code, character)
+                })
+                .sum();
+            if !materials.is_finite() {
+                continue;
+            }
+            let activity = Activity::Craft(item.code.clone());
+            candidates.extend(self.candidate(activity, craft.level, default_seconds, materials));
+        }
+
+        if skill == Skill::Fighting {
+            let mut fighter = Fighter::from_character(character, self.world.items());
+            fighter.hp = fighter.max_hp;
+            for monster in self.world.monsters().values() {
+                let prediction = self
+                    .simulator
+                    .simulate(&fighter, &Fighter::from_monster(monster));
+                if prediction.win_probability < MIN_WIN_PROBABILITY {
+                    continue;
+                }
+                let activity = Activity::Fight(monster.code.clone());
+                let seconds = prediction.expected_cooldown.as_secs_f64();
+                candidates.extend(self.candidate(
+                    activity,
+                    monster.level.max(0) as u32,
+                    seconds,
+                    0.0,
+                ));
+            }
+        }
+        candidates
+    }
+
+    fn candidate(
+        &self,
+        activity: Activity,
+        activity_level: u32,
+        default_seconds: f64,
+        material_actions: f64,
+    ) -> Option<Candidate> {
+        let observed = self.observed.get(&activity);
+        let (xp_per_action, seconds) = match observed {
+            Some(observed) => (observed.xp_per_action(), observed.seconds_per_action()),
+            None => (estimate_xp(activity_level), default_seconds),
+        };
+        (xp_per_action > 0.0).then(|| Candidate {
+            activity,
+            xp_per_action,
+            seconds_per_action: seconds.max(1.0)
+                + material_actions * f64::from(DEFAULT_ACTION_SECONDS),
+            material_actions,
+            estimated: observed.is_none(),
+        })
+    }
+}
+
+/// Rough XP of an activity before any has been measured, only meant to try
+/// higher level activities first.
+fn estimate_xp(activity_level: u32) -> f64 {
+    f64::from(10 + 2 * activity_level)
+}
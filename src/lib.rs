@@ -1,4 +1,21 @@
+pub mod action;
 pub mod api;
+pub mod bank;
+pub mod combat;
+pub mod crafting;
+pub mod dry_run;
+pub mod fleet;
+pub mod gear;
+pub mod goal;
+pub mod health;
+pub mod inventory;
+pub mod leveling;
+pub mod movement;
+pub mod persistence;
+pub mod runner;
+pub mod sourcing;
+pub mod statistics;
+pub mod world;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use crate::{
+    api::schema::r#type::{
+        character::{Character, Position},
+        map::{Map, MapContent, MapContentType},
+    },
+    world::World,
+};
+
+/// Movement cooldown per tile travelled, before speed bonuses.
+pub const SECONDS_PER_TILE: u32 = 5;
+
+/// Cooldown of a move between two positions for a character with `speed`.
+///
+/// Moves teleport in a straight line, so the cost only depends on the
+/// distance. Speed reduces the cooldown by that many percent.
+pub fn travel_time(from: &Position, to: &Position, speed: i32) -> Duration {
+    let seconds = from.distance(to) * SECONDS_PER_TILE;
+    let factor = 100u32.saturating_sub(speed.clamp(0, 100) as u32);
+    Duration::from_millis(u64::from(seconds) * u64::from(factor) * 10)
+}
+
+/// A place a trip has to stop at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// An exact tile.
+    Position(Position),
+    /// Any tile with this content, e.g. a specific resource.
+    Content(MapContent),
+    /// Any tile with this type of content, e.g. any bank.
+    Type(MapContentType),
+}
+
+/// One move of a [`Trip`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leg {
+    pub from: Position,
+    pub to: Position,
+    pub duration: Duration,
+}
+
+/// A sequence of moves visiting every [`Stop`] in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trip {
+    pub legs: Vec<Leg>,
+    pub total: Duration,
+}
+
+impl Trip {
+    /// Tiles visited, in order.
+    pub fn destinations(&self) -> impl Iterator<Item = Position> + '_ {
+        self.legs.iter().map(|leg| leg.to)
+    }
+}
+
+/// Travel cost computations for one character over a [`World`].
+#[derive(Clone, Copy, Debug)]
+pub struct Navigator<'a> {
+    world: &'a World,
+    speed: i32,
+}
+
+impl<'a> Navigator<'a> {
+    pub fn new(world: &'a World, character: &Character) -> Self {
+        Self::with_speed(world, character.speed)
+    }
+
+    pub fn with_speed(world: &'a World, speed: i32) -> Self {
+        Self { world, speed }
+    }
+
+    /// Cooldown of a move between two positions.
+    pub fn cost(&self, from: &Position, to: &Position) -> Duration {
+        travel_time(from, to, self.speed)
+    }
+
+    /// The candidate tile that is cheapest to reach from `from`.
+    pub fn cheapest<'m>(
+        &self,
+        from: &Position,
+        candidates: impl IntoIterator<Item = &'m Map>,
+    ) -> Option<(&'m Map, Duration)> {
+        candidates
+            .into_iter()
+            .map(|map| (map, self.cost(from, &map.position())))
+            .min_by_key(|(map, duration)| (*duration, map.x, map.y))
+    }
+
+    /// Tiles matching a stop.
+    pub fn candidates(&self, stop: &Stop) -> Vec<Position> {
+        match stop {
+            Stop::Position(position) => vec![*position],
+            Stop::Content(content) => self.world.tiles_with(content).map(Map::position).collect(),
+            Stop::Type(r#type) => self
+                .world
+                .tiles_of_type(*r#type)
+                .map(Map::position)
+                .collect(),
+        }
+    }
+
+    /// Plan a trip visiting `stops` in order, picking the tile of each stop
+    /// so that the total cooldown is minimal.
+    ///
+    /// Returns `None` when a stop has no matching tile.
+    pub fn plan_trip(&self, from: &Position, stops: &[Stop]) -> Option<Trip> {
+        // For each stop, the best total cost to reach each of its tiles and
+        // the index of the tile used at the previous stop.
+        let start = vec![(*from, Duration::ZERO, 0)];
+        let mut layers: Vec<Vec<(Position, Duration, usize)>> = Vec::with_capacity(stops.len());
+
+        for stop in stops {
+            let mut candidates = self.candidates(stop);
+            candidates.sort_by_key(|position| (position.x, position.y));
+            if candidates.is_empty() {
+                return None;
+            }
+            let previous = layers.last().unwrap_or(&start);
+            let layer = candidates
+                .into_iter()
+                .map(|to| {
+                    let (index, total) = previous
+                        .iter()
+                        .enumerate()
+                        .map(|(index, (at, total, _))| (index, *total + self.cost(at, &to)))
+                        .min_by_key(|(_, total)| *total)
+                        .expect("previous layer is never empty");
+                    (to, total, index)
+                })
+                .collect();
+            layers.push(layer);
+        }
+
+        let mut index = layers
+            .last()
+            .and_then(|layer| {
+                layer
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, (_, total, _))| *total)
+            })
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+
+        let mut destinations = Vec::with_capacity(layers.len());
+        for layer in layers.iter().rev() {
+            let (position, _, parent) = layer[index];
+            destinations.push(position);
+            index = parent;
+        }
+        destinations.reverse();
+
+        let mut trip = Trip::default();
+        let mut at = *from;
+        for to in destinations {
+            let duration = self.cost(&at, &to);
+            trip.legs.push(Leg {
+                from: at,
+                to,
+                duration,
+            });
+            trip.total += duration;
+            at = to;
+        }
+        Some(trip)
+    }
+}
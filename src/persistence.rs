@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+use crate::{
+    action::CharacterAction,
+    api::schema::r#type::{character::Character, time::Timestamp},
+    bank::BankState,
+    fleet::Fleet,
+    goal::Goal,
+    runner::Executor,
+};
+
+#[derive(Error, Debug)]
+pub enum PersistenceError {
+    #[error("failed to access the state file: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid state file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// What a character was doing when the snapshot was taken.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterSnapshot {
+    /// Actions not sent yet, in order.
+    pub queue: Vec<CharacterAction>,
+    /// Action sent and waiting for its response, if any.
+    #[serde(default)]
+    pub in_flight: Option<CharacterAction>,
+    /// Cooldown expiration of the character before the action in flight was
+    /// sent, telling whether the server completed it.
+    #[serde(default)]
+    pub cooldown_expiration: Option<Timestamp>,
+    /// Objective the queue was planned for, if any.
+    pub goal: Option<Goal>,
+}
+
+/// State of the bot needed to resume after a restart.
+///
+/// Queues and the bank mirror are captured from a [`Fleet`]; goals and
+/// counters belong to the caller, and are kept across captures.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub characters: BTreeMap<String, CharacterSnapshot>,
+    pub bank: Option<BankState>,
+    pub counters: BTreeMap<String, u64>,
+}
+
+impl Snapshot {
+    /// Record the queue of every character of `fleet` and its bank mirror.
+    pub fn capture<E: Executor + 'static>(&mut self, fleet: &Fleet<E>) {
+        for name in fleet.names() {
+            let Some(handle) = fleet.handle(name) else {
+                continue;
+            };
+            let checkpoint = handle.checkpoint();
+            let saved = self.characters.entry(name.to_string()).or_default();
+            saved.queue = checkpoint.pending;
+            saved.in_flight = checkpoint.in_flight;
+            saved.cooldown_expiration = checkpoint
+                .character
+                .and_then(|character| character.cooldown_info.cooldown_expiration);
+        }
+        self.bank = Some(fleet.bank());
+    }
+
+    pub fn set_goal(&mut self, name: &str, goal: Option<Goal>) {
+        self.characters.entry(name.to_string()).or_default().goal = goal;
+    }
+
+    pub fn counter(&self, counter: &str) -> u64 {
+        self.counters.get(counter).copied().unwrap_or_default()
+    }
+
+    /// Add `count` to a counter, creating it if needed.
+    pub fn count(&mut self, counter: &str, count: u64) {
+        *self.counters.entry(counter.to_string()).or_default() += count;
+    }
+
+    /// Saved queue of `character`, reconciled with its state fetched again.
+    ///
+    /// Only the action in flight may have been sent before the snapshot was
+    /// taken. A move is done when the character is at its destination; any
+    /// other action is done when the cooldown expiration changed since it
+    /// was sent, as every completed action starts a new cooldown. Actions not
+    /// done are queued again.
+    ///
+    /// Returns `None` when the outcome cannot be told, because the character
+    /// had no known cooldown before the action: the caller should plan again
+    /// from the goal rather than risk doing the action twice.
+    pub fn queue(&self, character: &Character) -> Option<Vec<CharacterAction>> {
+        let Some(saved) = self.characters.get(&character.name) else {
+            return Some(Vec::new());
+        };
+        let Some(in_flight) = &saved.in_flight else {
+            return Some(saved.queue.clone());
+        };
+        let done = match in_flight {
+            CharacterAction::Move(destination) => {
+                character.position.x == destination.x && character.position.y == destination.y
+            }
+            _ => {
+                let before = saved.cooldown_expiration.as_ref()?;
+                character.cooldown_info.cooldown_expiration.as_ref() != Some(before)
+            }
+        };
+        let resent = (!done).then(|| in_flight.clone());
+        Some(resent.into_iter().chain(saved.queue.clone()).collect())
+    }
+}
+
+/// A JSON file holding a [`Snapshot`].
+///
+/// Saving writes a temporary file next to it before renaming it over the
+/// previous one, so a crash while saving leaves the last snapshot intact.
+#[derive(Clone, Debug)]
+pub struct Store {
+    path: PathBuf,
+}
+
+impl Store {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the last snapshot saved, `None` when there is none yet.
+    pub fn load(&self) -> Result<Option<Snapshot>, PersistenceError> {
+        match fs::read_to_string(&self.path) {
+            Ok(data) => Ok(Some(serde_json::from_str(&data)?)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, snapshot: &Snapshot) -> Result<(), PersistenceError> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+
+        let mut file = File::create(&temporary)?;
+        serde_json::to_writer_pretty(&mut file, snapshot)?;
+        file.flush()?;
+        file.sync_all()?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
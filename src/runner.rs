@@ -0,0 +1,366 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{mpsc, watch, Notify};
+
+use crate::{
+    action::{Action, ActionOutcome, CharacterAction},
+    api::{
+        schema::{
+            r#type::{character::Character, time::ServerClock},
+            response::{ResponseData, ResponseError},
+        },
+        Api,
+    },
+};
+
+/// Something able to perform character actions, the [`Api`] or a stand-in.
+pub trait Executor: Send + Sync {
+    fn execute(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> impl Future<Output = Result<ResponseData, ResponseError>> + Send;
+
+    /// Actions to perform before `action`, none by default. A runner queues
+    /// them ahead of it, so that each waits for its own cooldown and can be
+    /// paused or cancelled.
+    fn before(&self, _name: &str, _action: &CharacterAction) -> Vec<CharacterAction> {
+        Vec::new()
+    }
+}
+
+impl Executor for Api {
+    async fn execute(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        action.execute(self, name).await
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunState {
+    Running,
+    /// No new action is started until the runner is resumed.
+    Paused,
+    /// The runner stops for good.
+    Cancelled,
+}
+
+/// The outcome of one queued action.
+#[derive(Debug)]
+pub struct ActionResult {
+    /// Name of the character that performed the action.
+    pub name: String,
+    pub action: CharacterAction,
+    pub result: Result<ResponseData, ResponseError>,
+}
+
+impl ActionResult {
+    /// Character state after the action, if it succeeded.
+    pub fn character(&self) -> Option<&Character> {
+        self.result.as_ref().ok().map(ActionOutcome::character)
+    }
+}
+
+/// Where a runner is in its queue, as seen from other tasks.
+#[derive(Clone, Debug, Default)]
+pub struct Checkpoint {
+    /// Last known state of the character, before the action in flight.
+    pub character: Option<Character>,
+    /// Action sent and waiting for its response, if any.
+    pub in_flight: Option<CharacterAction>,
+    /// Actions not started yet, in order.
+    pub pending: Vec<CharacterAction>,
+}
+
+/// Kept behind a single lock, so that an action is always either pending,
+/// in flight or reflected in the character.
+#[derive(Default)]
+struct Progress {
+    queue: VecDeque<CharacterAction>,
+    in_flight: Option<CharacterAction>,
+    character: Option<Character>,
+}
+
+struct Shared {
+    progress: Mutex<Progress>,
+    state: watch::Sender<RunState>,
+    queued: Notify,
+}
+
+impl Shared {
+    /// Change the state, unless the runner has been cancelled.
+    fn set_state(&self, state: RunState) {
+        self.state.send_if_modified(|current| {
+            let changed = *current != RunState::Cancelled && *current != state;
+            if changed {
+                *current = state;
+            }
+            changed
+        });
+    }
+}
+
+/// Controls a [`CharacterRunner`] from other tasks.
+#[derive(Clone)]
+pub struct RunnerHandle {
+    shared: Arc<Shared>,
+}
+
+impl RunnerHandle {
+    /// Queue an action after the pending ones.
+    pub fn push(&self, action: impl Into<CharacterAction>) {
+        self.shared
+            .progress
+            .lock()
+            .unwrap()
+            .queue
+            .push_back(action.into());
+        self.shared.queued.notify_one();
+    }
+
+    pub fn extend(&self, actions: impl IntoIterator<Item = CharacterAction>) {
+        self.shared.progress.lock().unwrap().queue.extend(actions);
+        self.shared.queued.notify_one();
+    }
+
+    /// Drop every pending action, returning them.
+    pub fn clear(&self) -> Vec<CharacterAction> {
+        self.shared
+            .progress
+            .lock()
+            .unwrap()
+            .queue
+            .drain(..)
+            .collect()
+    }
+
+    /// Actions not started yet, in order.
+    pub fn pending(&self) -> Vec<CharacterAction> {
+        let progress = self.shared.progress.lock().unwrap();
+        progress.queue.iter().cloned().collect()
+    }
+
+    /// Action sent and waiting for its response, if any.
+    pub fn in_flight(&self) -> Option<CharacterAction> {
+        self.shared.progress.lock().unwrap().in_flight.clone()
+    }
+
+    /// Last known state of the character, from the latest response.
+    pub fn character(&self) -> Option<Character> {
+        self.shared.progress.lock().unwrap().character.clone()
+    }
+
+    /// The character, in-flight action and pending actions, all read at once.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let progress = self.shared.progress.lock().unwrap();
+        Checkpoint {
+            character: progress.character.clone(),
+            in_flight: progress.in_flight.clone(),
+            pending: progress.queue.iter().cloned().collect(),
+        }
+    }
+
+    pub fn state(&self) -> RunState {
+        *self.shared.state.borrow()
+    }
+
+    /// Stop starting new actions. The action in flight, if any, completes.
+    pub fn pause(&self) {
+        self.shared.set_state(RunState::Paused);
+    }
+
+    pub fn resume(&self) {
+        self.shared.set_state(RunState::Running);
+    }
+
+    /// Stop the runner, leaving pending actions in the queue.
+    pub fn cancel(&self) {
+        self.shared.set_state(RunState::Cancelled);
+    }
+}
+
+/// Executes the queued actions of one character in order.
+///
+/// Each action starts once the cooldown of the previous response has
+/// elapsed. When an action fails the runner puts it back at the front of the
+/// queue and pauses, so that observers can fix the queue before resuming it. Actions the executor asks for
+/// [`before`](Executor::before) an action are queued ahead of it.
+pub struct CharacterRunner<E = Api> {
+    name: String,
+    executor: Arc<E>,
+    shared: Arc<Shared>,
+    results: mpsc::UnboundedSender<ActionResult>,
+    cooldown: Duration,
+}
+
+impl<E: Executor> CharacterRunner<E> {
+    /// Create a runner for character `name`, with a handle to control it and
+    /// the receiving end of its results.
+    pub fn new(
+        name: impl Into<String>,
+        executor: Arc<E>,
+    ) -> (Self, RunnerHandle, mpsc::UnboundedReceiver<ActionResult>) {
+        let (results, receiver) = mpsc::unbounded_channel();
+        let (runner, handle) = Self::with_sender(name, executor, results);
+        (runner, handle, receiver)
+    }
+
+    /// Create a runner sending its results to an existing channel, e.g. one
+    /// shared by several runners.
+    pub fn with_sender(
+        name: impl Into<String>,
+        executor: Arc<E>,
+        results: mpsc::UnboundedSender<ActionResult>,
+    ) -> (Self, RunnerHandle) {
+        let shared = Arc::new(Shared {
+            progress: Mutex::new(Progress::default()),
+            state: watch::Sender::new(RunState::Running),
+            queued: Notify::new(),
+        });
+        let handle = RunnerHandle {
+            shared: shared.clone(),
+        };
+        let runner = Self {
+            name: name.into(),
+            executor,
+            shared,
+            results,
+            cooldown: Duration::ZERO,
+        };
+        (runner, handle)
+    }
+
+    /// Start with a known character state instead of none.
+    pub fn with_character(self, character: Character) -> Self {
+        self.shared.progress.lock().unwrap().character = Some(character);
+        self
+    }
+
+    /// Wait before the first action, e.g. for a cooldown started before the
+    /// runner was created.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Run until cancelled, waiting for new actions when the queue is empty.
+    pub async fn run(self) {
+        let mut state = self.shared.state.subscribe();
+        if !wait(&mut state, self.cooldown).await {
+            return;
+        }
+        // Action whose prerequisites were just queued, not to be checked again.
+        let mut prepared: Option<CharacterAction> = None;
+        loop {
+            let current = *state.borrow_and_update();
+            match current {
+                RunState::Cancelled => return,
+                RunState::Paused => {
+                    let _ = state.changed().await;
+                    continue;
+                }
+                RunState::Running => {}
+            }
+
+            let next = {
+                let mut progress = self.shared.progress.lock().unwrap();
+                let next = progress.queue.pop_front();
+                if let Some(action) = &next {
+                    let before = if prepared.as_ref() == Some(action) {
+                        prepared = None;
+                        Vec::new()
+                    } else {
+                        self.executor.before(&self.name, action)
+                    };
+                    if !before.is_empty() {
+                        progress.queue.push_front(action.clone());
+                        for inserted in before.into_iter().rev() {
+                            progress.queue.push_front(inserted);
+                        }
+                        prepared = next;
+                        continue;
+                    }
+                }
+                progress.in_flight.clone_from(&next);
+                next
+            };
+            let Some(action) = next else {
+                tokio::select! {
+                    _ = self.shared.queued.notified() => {}
+                    _ = state.changed() => {}
+                }
+                continue;
+            };
+
+            let result = self.executor.execute(&self.name, &action).await;
+            let cooldown = {
+                let mut progress = self.shared.progress.lock().unwrap();
+                progress.in_flight = None;
+                match &result {
+                    Ok(data) => {
+                        progress.character = Some(data.character().clone());
+                        Duration::from_secs(data.cooldown().remaining_seconds.max(0) as u64)
+                    }
+                    Err(_) => {
+                        progress.queue.push_front(action.clone());
+                        self.shared.set_state(RunState::Paused);
+                        Duration::ZERO
+                    }
+                }
+            };
+            let _ = self.results.send(ActionResult {
+                name: self.name.clone(),
+                action,
+                result,
+            });
+
+            if !wait(&mut state, cooldown).await {
+                return;
+            }
+        }
+    }
+}
+
+/// Sleep for `duration`, returning `false` as soon as the runner is
+/// cancelled.
+async fn wait(state: &mut watch::Receiver<RunState>, duration: Duration) -> bool {
+    let sleep = tokio::time::sleep(duration);
+    tokio::pin!(sleep);
+    loop {
+        tokio::select! {
+            _ = &mut sleep => return true,
+            _ = state.changed() => {
+                if *state.borrow_and_update() == RunState::Cancelled {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// Cooldown left on a character fetched from the API, measured with the
+/// server `clock`.
+///
+/// A missing expiration counts as no cooldown. When it cannot be read, e.g.
+/// without the `chrono` feature, the whole cooldown of the last action is
+/// assumed to remain.
+pub fn remaining_cooldown(character: &Character, clock: &ServerClock) -> Duration {
+    let cooldown = &character.cooldown_info;
+    match &cooldown.cooldown_expiration {
+        Some(expiration) => clock
+            .until(expiration)
+            .unwrap_or_else(|| Duration::from_secs(cooldown.cooldown.max(0) as u64)),
+        None => Duration::ZERO,
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use crate::{
+    api::schema::r#type::{
+        character::{Character, Position, Skill},
+        code::{MonsterCode, ResourceCode},
+        item::{ItemCode, ItemComponent},
+        map::{Map, MapContent, MapContentType},
+    },
+    statistics::DropRate,
+    world::World,
+};
+
+/// One way of obtaining an item.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    /// Gathered from a resource.
+    Resource {
+        resource: ResourceCode,
+        skill: Skill,
+        level: u32,
+        drop: DropRate,
+        tiles: Vec<Position>,
+    },
+    /// Dropped by a monster.
+    Monster {
+        monster: MonsterCode,
+        level: u32,
+        drop: DropRate,
+        tiles: Vec<Position>,
+    },
+    /// Crafted from other items.
+    Craft {
+        skill: Skill,
+        level: u32,
+        quantity: u32,
+        items: Vec<ItemComponent>,
+    },
+}
+
+impl Source {
+    /// Skill and level needed to use this source.
+    pub fn requirement(&self) -> (Skill, u32) {
+        match self {
+            Source::Resource { skill, level, .. } | Source::Craft { skill, level, .. } => {
+                (*skill, *level)
+            }
+            Source::Monster { level, .. } => (Skill::Fighting, *level),
+        }
+    }
+
+    /// Tiles where the source can be found. Crafts happen in workshops and
+    /// return no tile.
+    pub fn tiles(&self) -> &[Position] {
+        match self {
+            Source::Resource { tiles, .. } | Source::Monster { tiles, .. } => tiles,
+            Source::Craft { .. } => &[],
+        }
+    }
+}
+
+/// A source with its cost for a given character.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankedSource<'a> {
+    pub source: &'a Source,
+    /// Expected gather, fight or craft actions per item obtained, including
+    /// the actions needed to obtain craft materials.
+    pub actions_per_unit: f64,
+    /// Whether the character meets the skill requirement.
+    pub available: bool,
+}
+
+/// Index of every known way to obtain each item.
+///
+/// Items with no source are only obtainable from tasks, NPCs or events.
+#[derive(Clone, Debug, Default)]
+pub struct Sourcing {
+    sources: HashMap<ItemCode, Vec<Source>>,
+}
+
+impl Sourcing {
+    pub fn new(world: &World) -> Self {
+        let mut sources: HashMap<ItemCode, Vec<Source>> = HashMap::new();
+        let tiles = |content: MapContent| -> Vec<Position> {
+            let mut tiles: Vec<_> = world.tiles_with(&content).map(Map::position).collect();
+            tiles.sort_by_key(|position| (position.x, position.y));
+            tiles
+        };
+
+        for resource in world.resources().values() {
+            let content = MapContent::new(MapContentType::Resource, resource.code.clone().into());
+            let tiles = tiles(content);
+            for drop in &resource.drops {
+                sources
+                    .entry(drop.code.clone())
+                    .or_default()
+                    .push(Source::Resource {
+                        resource: resource.code.clone(),
+                        skill: resource.skill,
+                        level: resource.level.max(0) as u32,
+                        drop: drop.into(),
+                        tiles: tiles.clone(),
+                    });
+            }
+        }
+
+        for monster in world.monsters().values() {
+            let content = MapContent::new(MapContentType::Monster, monster.code.clone().into());
+            let tiles = tiles(content);
+            for drop in &monster.drops {
+                sources
+                    .entry(drop.code.clone())
+                    .or_default()
+                    .push(Source::Monster {
+                        monster: monster.code.clone(),
+                        level: monster.level.max(0) as u32,
+                        drop: drop.into(),
+                        tiles: tiles.clone(),
+                    });
+            }
+        }
+
+        for item in world.items().values() {
+            if let Some(craft) = &item.craft {
+                sources
+                    .entry(item.code.clone())
+                    .or_default()
+                    .push(Source::Craft {
+                        skill: craft.skill,
+                        level: craft.level,
+                        quantity: craft.quantity,
+                        items: craft.items.clone(),
+                    });
+            }
+        }
+
+        Self { sources }
+    }
+
+    /// Every known source of an item.
+    pub fn sources(&self, code: &ItemCode) -> &[Source] {
+        self.sources
+            .get(code)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Sources of an item from the cheapest to the most expensive for
+    /// `character`, sources the character cannot use yet coming last.
+    pub fn rank(&self, code: &ItemCode, character: &Character) -> Vec<RankedSource<'_>> {
+        let mut cache = HashMap::new();
+        let mut ranked: Vec<_> = self
+            .sources(code)
+            .iter()
+            .map(|source| {
+                let (skill, level) = source.requirement();
+                RankedSource {
+                    source,
+                    actions_per_unit: self.actions_per_unit(
+                        source,
+                        character,
+                        &mut cache,
+                        &mut vec![code.clone()],
+                    ),
+                    available: character.skill(skill).level >= level,
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.available
+                .cmp(&a.available)
+                .then(a.actions_per_unit.total_cmp(&b.actions_per_unit))
+        });
+        ranked
+    }
+
+    /// Cheapest expected number of actions to obtain one `code` with a
+    /// source the character can use, `f64::INFINITY` when there is none.
+    pub fn best_actions_per_unit(&self, code: &ItemCode, character: &Character) -> f64 {
+        self.best(code, character, &mut HashMap::new(), &mut Vec::new())
+            .0
+    }
+
+    fn best(
+        &self,
+        code: &ItemCode,
+        character: &Character,
+        cache: &mut HashMap<ItemCode, f64>,
+        path: &mut Vec<ItemCode>,
+    ) -> (f64, bool) {
+        if let Some(actions) = cache.get(code) {
+            return (*actions, false);
+        }
+        if path.contains(code) {
+            return (f64::INFINITY, true);
+        }
+        path.push(code.clone());
+        let (best, cut) = self
+            .sources(code)
+            .iter()
+            .filter(|source| {
+                let (skill, level) = source.requirement();
+                character.skill(skill).level >= level
+            })
+            .map(|source| self.estimate(source, character, cache, path))
+            .fold((f64::INFINITY, false), |(best, cut), (actions, cycle)| {
+                (best.min(actions), cut || cycle)
+            });
+        path.pop();
+        // A cost found while a cycle was cut depends on the path taken to
+        // reach the item, so it is not reused from elsewhere.
+        if !cut {
+            cache.insert(code.clone(), best);
+        }
+        (best, cut)
+    }
+
+    fn actions_per_unit(
+        &self,
+        source: &Source,
+        character: &Character,
+        cache: &mut HashMap<ItemCode, f64>,
+        path: &mut Vec<ItemCode>,
+    ) -> f64 {
+        self.estimate(source, character, cache, path).0
+    }
+
+    /// Expected actions per unit of `source`, and whether a cycle through
+    /// `path` was cut to compute it.
+    fn estimate(
+        &self,
+        source: &Source,
+        character: &Character,
+        cache: &mut HashMap<ItemCode, f64>,
+        path: &mut Vec<ItemCode>,
+    ) -> (f64, bool) {
+        match source {
+            Source::Resource { drop, .. } | Source::Monster { drop, .. } => {
+                (1.0 / drop.expected_per_action(), false)
+            }
+            Source::Craft {
+                quantity, items, ..
+            } => {
+                let mut cut = false;
+                let materials: f64 = items
+                    .iter()
+                    .map(|item| {
+                        let (actions, cycle) = self.best(&item.code, character, cache, path);
+                        cut |= cycle;
+                        f64::from(item.quantity) * actions
+                    })
+                    .sum();
+                ((1.0 + materials) / f64::from((*quantity).max(1)), cut)
+            }
+        }
+    }
+}
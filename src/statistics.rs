@@ -0,0 +1,145 @@
+use crate::api::schema::r#type::{
+    item::ResourceDrop,
+    monster::{Monster, MonsterDrop},
+};
+
+/// Highest confidence [`DropRate::actions_for`] aims for. Closer to 1, the
+/// rounding of the distribution may never let it be reached.
+pub const MAX_CONFIDENCE: f64 = 1.0 - 1e-9;
+
+/// A "1 in `rate`" chance to get between `min_quantity` and `max_quantity`
+/// items, the quantity being uniformly distributed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DropRate {
+    pub rate: u32,
+    pub min_quantity: u32,
+    pub max_quantity: u32,
+}
+
+impl From<&ResourceDrop> for DropRate {
+    fn from(drop: &ResourceDrop) -> Self {
+        Self {
+            rate: drop.rate,
+            min_quantity: drop.min_quantity,
+            max_quantity: drop.max_quantity,
+        }
+    }
+}
+
+impl From<&MonsterDrop> for DropRate {
+    fn from(drop: &MonsterDrop) -> Self {
+        Self {
+            rate: drop.rate.max(0) as u32,
+            min_quantity: drop.min_quantity.max(0) as u32,
+            max_quantity: drop.max_quantity.max(0) as u32,
+        }
+    }
+}
+
+impl DropRate {
+    /// Chance for a single action to drop the item.
+    pub fn probability(&self) -> f64 {
+        1.0 / f64::from(self.rate.max(1))
+    }
+
+    /// Average quantity of a successful drop.
+    pub fn expected_quantity(&self) -> f64 {
+        f64::from(self.min_quantity + self.max_quantity.max(self.min_quantity)) / 2.0
+    }
+
+    /// Average number of items obtained per action.
+    pub fn expected_per_action(&self) -> f64 {
+        self.probability() * self.expected_quantity()
+    }
+
+    /// Average number of items obtained after `actions` actions.
+    pub fn expected_after(&self, actions: u32) -> f64 {
+        f64::from(actions) * self.expected_per_action()
+    }
+
+    /// Average number of actions needed to obtain `quantity` items.
+    pub fn expected_actions_for(&self, quantity: u32) -> f64 {
+        f64::from(quantity) / self.expected_per_action()
+    }
+
+    /// Probability of obtaining at least `quantity` items in `actions` actions.
+    pub fn probability_at_least(&self, quantity: u32, actions: u32) -> f64 {
+        let mut distribution = Distribution::new(*self, quantity);
+        (0..actions).for_each(|_| distribution.step());
+        distribution.reached()
+    }
+
+    /// Smallest number of actions after which at least `quantity` items have
+    /// been obtained with probability `confidence` (between 0 and 1, capped
+    /// at [`MAX_CONFIDENCE`]). Stops early if the probability stops
+    /// increasing before reaching it.
+    ///
+    /// Returns `None` when the item can never drop.
+    pub fn actions_for(&self, quantity: u32, confidence: f64) -> Option<u32> {
+        if quantity == 0 {
+            return Some(0);
+        }
+        if self.max_quantity == 0 {
+            return None;
+        }
+        let confidence = confidence.clamp(0.0, MAX_CONFIDENCE);
+        let mut distribution = Distribution::new(*self, quantity);
+        let mut actions = 0;
+        let mut reached = distribution.reached();
+        while reached < confidence {
+            distribution.step();
+            actions += 1;
+            let previous = std::mem::replace(&mut reached, distribution.reached());
+            if previous > 0.0 && reached <= previous {
+                break;
+            }
+        }
+        Some(actions)
+    }
+}
+
+/// Distribution of the number of items obtained, capped at a target.
+struct Distribution {
+    drop: DropRate,
+    /// `probabilities[k]` is the chance of holding exactly `k` items, the
+    /// last bucket holding the chance of having reached the target.
+    probabilities: Vec<f64>,
+}
+
+impl Distribution {
+    fn new(drop: DropRate, target: u32) -> Self {
+        let mut probabilities = vec![0.0; target as usize + 1];
+        probabilities[0] = 1.0;
+        Self {
+            drop,
+            probabilities,
+        }
+    }
+
+    fn step(&mut self) {
+        let target = self.probabilities.len() - 1;
+        let p = self.drop.probability();
+        let min = self.drop.min_quantity as usize;
+        let max = (self.drop.max_quantity as usize).max(min);
+        let per_quantity = p / (max - min + 1) as f64;
+
+        let mut next = vec![0.0; self.probabilities.len()];
+        next[target] = self.probabilities[target];
+        for (held, probability) in self.probabilities[..target].iter().enumerate() {
+            next[held] += probability * (1.0 - p);
+            for quantity in min..=max {
+                next[(held + quantity).min(target)] += probability * per_quantity;
+            }
+        }
+        self.probabilities = next;
+    }
+
+    fn reached(&self) -> f64 {
+        self.probabilities[self.probabilities.len() - 1]
+    }
+}
+
+/// Average gold dropped by a monster when it is defeated.
+pub fn expected_gold(monster: &Monster) -> f64 {
+    f64::from(monster.min_gold + monster.max_gold.max(monster.min_gold)) / 2.0
+}
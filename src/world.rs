@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::api::{
+    schema::{
+        r#type::{
+            character::Position,
+            code::{MonsterCode, ResourceCode},
+            item::{Item, ItemCode, Resource},
+            map::{Map, MapContent, MapContentType},
+            monster::Monster,
+        },
+        response::ResponseError,
+    },
+    Api,
+};
+
+/// In-memory model of the game world.
+///
+/// Tiles are indexed by coordinate and by [`MapContent`] so questions such as
+/// "where is the nearest copper rocks" do not need to scan every map.
+#[derive(Clone, Debug, Default)]
+pub struct World {
+    tiles: HashMap<Position, Map>,
+    content: HashMap<MapContent, Vec<Position>>,
+    monsters: HashMap<MonsterCode, Monster>,
+    resources: HashMap<ResourceCode, Resource>,
+    items: HashMap<ItemCode, Item>,
+}
+
+impl World {
+    pub fn new(
+        maps: Vec<Map>,
+        monsters: Vec<Monster>,
+        resources: Vec<Resource>,
+        items: Vec<Item>,
+    ) -> Self {
+        let mut world = Self {
+            monsters: monsters
+                .into_iter()
+                .map(|monster| (monster.code.clone(), monster))
+                .collect(),
+            resources: resources
+                .into_iter()
+                .map(|resource| (resource.code.clone(), resource))
+                .collect(),
+            items: items
+                .into_iter()
+                .map(|item| (item.code.clone(), item))
+                .collect(),
+            ..Default::default()
+        };
+        world.update_tiles(maps);
+        world
+    }
+
+    /// Download maps, monsters, resources and items and build the world.
+    pub async fn fetch(api: &Api) -> Result<Self, ResponseError> {
+        Ok(Self::new(
+            api.maps().await?,
+            api.monsters().await?,
+            api.resources().await?,
+            api.items().await?,
+        ))
+    }
+
+    /// Replace a single tile, e.g. when an event spawns or despawns content.
+    pub fn update_tile(&mut self, map: Map) {
+        let position = map.position();
+        if let Some(previous) = self.tiles.remove(&position) {
+            if let Some(content) = previous.content {
+                self.unindex(&content, &position);
+            }
+        }
+        if let Some(content) = &map.content {
+            self.content
+                .entry(content.clone())
+                .or_default()
+                .push(position);
+        }
+        self.tiles.insert(position, map);
+    }
+
+    /// Replace several tiles at once.
+    pub fn update_tiles(&mut self, maps: impl IntoIterator<Item = Map>) {
+        maps.into_iter().for_each(|map| self.update_tile(map));
+    }
+
+    fn unindex(&mut self, content: &MapContent, position: &Position) {
+        if let Some(positions) = self.content.get_mut(content) {
+            positions.retain(|p| p != position);
+            if positions.is_empty() {
+                self.content.remove(content);
+            }
+        }
+    }
+
+    /// The tile at a position.
+    pub fn tile(&self, position: &Position) -> Option<&Map> {
+        self.tiles.get(position)
+    }
+
+    /// Every known tile, in no particular order.
+    pub fn tiles(&self) -> impl Iterator<Item = &Map> {
+        self.tiles.values()
+    }
+
+    /// Tiles holding exactly this content.
+    pub fn tiles_with<'a>(&'a self, content: &MapContent) -> impl Iterator<Item = &'a Map> {
+        self.content
+            .get(content)
+            .into_iter()
+            .flatten()
+            .filter_map(|position| self.tiles.get(position))
+    }
+
+    /// Tiles holding any content of this type, e.g. every bank.
+    pub fn tiles_of_type(&self, r#type: MapContentType) -> impl Iterator<Item = &Map> {
+        self.content
+            .iter()
+            .filter(move |(content, _)| content.r#type == r#type)
+            .flat_map(|(_, positions)| positions)
+            .filter_map(|position| self.tiles.get(position))
+    }
+
+    /// The closest tile to `from` holding this content.
+    pub fn nearest(&self, from: &Position, content: &MapContent) -> Option<&Map> {
+        nearest(from, self.tiles_with(content))
+    }
+
+    /// The closest tile to `from` holding any content of this type.
+    pub fn nearest_of_type(&self, from: &Position, r#type: MapContentType) -> Option<&Map> {
+        nearest(from, self.tiles_of_type(r#type))
+    }
+
+    pub fn monster(&self, code: &MonsterCode) -> Option<&Monster> {
+        self.monsters.get(code)
+    }
+
+    pub fn monsters(&self) -> &HashMap<MonsterCode, Monster> {
+        &self.monsters
+    }
+
+    pub fn resource(&self, code: &ResourceCode) -> Option<&Resource> {
+        self.resources.get(code)
+    }
+
+    pub fn resources(&self) -> &HashMap<ResourceCode, Resource> {
+        &self.resources
+    }
+
+    pub fn item(&self, code: &ItemCode) -> Option<&Item> {
+        self.items.get(code)
+    }
+
+    pub fn items(&self) -> &HashMap<ItemCode, Item> {
+        &self.items
+    }
+}
+
+/// Closest tile by distance, ties broken by coordinates so results are stable.
+fn nearest<'a>(from: &Position, tiles: impl Iterator<Item = &'a Map>) -> Option<&'a Map> {
+    tiles.min_by_key(|map| (from.distance(&map.position()), map.x, map.y))
+}
@@ -0,0 +1,116 @@
+use std::fs;
+
+use artifacts_rs::{
+    action::{Action, ActionOutcome, CharacterAction, Craft, Equip, Fight, Move, Rest},
+    api::schema::{
+        r#type::item::ItemSlot,
+        response::my_characters::{
+            CharacterBankExpansionData, CharacterCraftData, CharacterEquipData, CharacterFightData,
+            CharacterGatherData, CharacterGoldTransactionData, CharacterItemTransactionData,
+            CharacterMovementData, CharacterRecycleData, CharacterRestData, CharacterUseItemData,
+        },
+        response::ResponseData,
+        SchemaWrapper,
+    },
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+fn load<T: DeserializeOwned>(file_name: &str) -> T {
+    let path = format!("tests/data/my_characters/{}", file_name);
+    let data = fs::read_to_string(path).expect("Failed to read test data file");
+    serde_json::from_str::<SchemaWrapper<T>>(&data)
+        .expect("Failed to deserialize test data")
+        .data
+}
+
+/// Compile-time check that every action response exposes its outcome.
+fn outcome<A: Action>(_: &A, data: &A::ResponseData) -> (i32, String) {
+    (data.cooldown().total_seconds, data.character().name.clone())
+}
+
+fn check<T: DeserializeOwned + ActionOutcome>(file_name: &str) {
+    let data: T = load(file_name);
+    assert_eq!(data.character().name, "string", "{file_name}");
+    assert_eq!(data.cooldown().total_seconds, 0, "{file_name}");
+}
+
+#[test]
+fn test_action_serde() {
+    let actions: Vec<CharacterAction> = vec![
+        Move::new(1, -2).into(),
+        Rest.into(),
+        Fight.into(),
+        Equip::new("copper_dagger".parse().unwrap(), ItemSlot::Weapon).into(),
+        Craft::new("copper".parse().unwrap(), 3).into(),
+    ];
+    let value = serde_json::to_value(&actions).unwrap();
+    assert_eq!(
+        value,
+        json!([
+            { "action": "move", "x": 1, "y": -2 },
+            { "action": "rest" },
+            { "action": "fight" },
+            { "action": "equip", "code": "copper_dagger", "slot": "weapon", "quantity": 1 },
+            { "action": "craft", "code": "copper", "quantity": 3 },
+        ])
+    );
+    assert_eq!(
+        serde_json::from_value::<Vec<CharacterAction>>(value).unwrap(),
+        actions
+    );
+
+    assert!(serde_json::from_value::<CharacterAction>(json!({ "action": "fly" })).is_err());
+    assert!(serde_json::from_value::<CharacterAction>(
+        json!({ "action": "craft", "code": "not a code", "quantity": 1 })
+    )
+    .is_err());
+}
+
+#[test]
+fn test_action_outcome() {
+    let data: CharacterFightData = load("character_fight.json");
+    assert_eq!(outcome(&Fight, &data), (0, "string".to_string()));
+    let data: CharacterMovementData = load("character_movement.json");
+    assert_eq!(outcome(&Move::new(0, 0), &data).1, data.character.name);
+
+    check::<CharacterRestData>("character_rest.json");
+    check::<CharacterEquipData>("character_equip.json");
+    check::<CharacterUseItemData>("character_use_item.json");
+    check::<CharacterGatherData>("character_gather.json");
+    check::<CharacterCraftData>("character_craft.json");
+    check::<CharacterRecycleData>("character_recycle.json");
+    check::<CharacterGoldTransactionData>("character_deposit_bank_gold.json");
+    check::<CharacterItemTransactionData>("character_withdraw_bank_item.json");
+    check::<CharacterBankExpansionData>("character_buy_bank_expansion.json");
+}
+
+#[test]
+fn test_response_data() {
+    let data = fs::read_to_string("tests/data/simulated_fights/chicken.json").unwrap();
+    let fight = serde_json::from_str::<SchemaWrapper<CharacterFightData>>(&data)
+        .unwrap()
+        .data;
+    assert_eq!(
+        fight.describe(),
+        "won in 9 turns: 12 XP, 2 gold, dropped 1 raw_chicken"
+    );
+
+    let responses: Vec<ResponseData> = vec![
+        fight.into(),
+        load::<CharacterRestData>("character_rest.json").into(),
+        load::<CharacterGatherData>("character_gather.json").into(),
+    ];
+    let json = serde_json::to_value(&responses).unwrap();
+    assert_eq!(json[0]["type"], "character_fight");
+    assert_eq!(json[1]["type"], "character_rest");
+    assert_eq!(json[0]["data"]["fight"]["turns"], 9);
+
+    let replayed: Vec<ResponseData> = serde_json::from_value(json).unwrap();
+    assert!(matches!(replayed[0], ResponseData::CharacterFight(_)));
+    assert_eq!(replayed[0].cooldown().total_seconds, 18);
+    assert_eq!(replayed[0].character().name, "fighter");
+    assert_eq!(replayed[0].describe(), responses[0].describe());
+    assert!(replayed[1].describe().starts_with("restored "));
+    assert!(replayed[2].describe().starts_with("gathered "));
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use std::{fs, time::Duration};
+
+use artifacts_rs::{
+    api::schema::{
+        response::{
+            my_characters::{
+                CharacterBankExpansionData, CharacterGoldTransactionData,
+                CharacterItemTransactionData, CharacterRestData,
+            },
+            ResponseData,
+        },
+        SchemaWrapper,
+    },
+    bank::{BankState, Drift},
+};
+use serde::de::DeserializeOwned;
+
+fn load<T: DeserializeOwned>(file_name: &str) -> T {
+    let path = format!("tests/data/my_characters/{}", file_name);
+    let data = fs::read_to_string(path).expect("Failed to read test data file");
+    serde_json::from_str::<SchemaWrapper<T>>(&data)
+        .expect("Failed to deserialize test data")
+        .data
+}
+
+#[test]
+fn test_bank_state() {
+    let mut bank = BankState::new(
+        &common::bank_details(100, 50),
+        &common::items(&[("copper", 10), ("ash_wood", 0)]),
+    );
+    assert_eq!(bank.quantity("copper"), 10);
+    assert_eq!(bank.quantity("ash_wood"), 0);
+    assert_eq!(bank.to_components(), common::items(&[("copper", 10)]));
+    assert_eq!(bank.gold(), 100);
+    assert_eq!(bank.free_slots(), 49);
+
+    // Item transactions return the whole bank.
+    let data: CharacterItemTransactionData = load("character_withdraw_bank_item.json");
+    assert!(bank.update(&data.into()));
+    assert_eq!(bank.quantity("copper"), 0);
+    assert_eq!(bank.quantity("string"), 1);
+
+    let data: CharacterGoldTransactionData = load("character_deposit_bank_gold.json");
+    assert!(bank.update(&data.into()));
+    assert_eq!(bank.gold(), 0);
+
+    let data: CharacterBankExpansionData = load("character_buy_bank_expansion.json");
+    assert!(bank.update(&ResponseData::from(data)));
+    assert_eq!(bank.expansions(), 1);
+    // The new slot count is unknown until the next sync.
+    assert_eq!(bank.slots(), 50);
+    assert!(bank.needs_sync(Duration::from_secs(3600)));
+
+    let data: CharacterRestData = load("character_rest.json");
+    assert!(!bank.update(&data.into()));
+}
+
+#[test]
+fn test_reconcile() {
+    let mut bank = BankState::new(
+        &common::bank_details(100, 50),
+        &common::items(&[("copper", 10)]),
+    );
+    assert!(bank
+        .reconcile(
+            &common::bank_details(100, 50),
+            &common::items(&[("copper", 10)])
+        )
+        .is_empty());
+
+    let drift = bank.reconcile(
+        &common::bank_details(250, 50),
+        &common::items(&[("iron", 2), ("copper", 8)]),
+    );
+    assert_eq!(
+        drift,
+        [
+            Drift::Item {
+                code: "copper".parse().unwrap(),
+                expected: 10,
+                actual: 8
+            },
+            Drift::Item {
+                code: "iron".parse().unwrap(),
+                expected: 0,
+                actual: 2
+            },
+            Drift::Gold {
+                expected: 100,
+                actual: 250
+            },
+        ]
+    );
+    assert_eq!(bank.quantity("copper"), 8);
+    assert_eq!(bank.quantity("iron"), 2);
+    assert_eq!(bank.gold(), 250);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_needs_sync() {
+    let every = Duration::from_secs(300);
+    let mut bank = BankState::new(&common::bank_details(0, 50), &[]);
+    assert!(!bank.needs_sync(every));
+
+    tokio::time::sleep(every).await;
+    assert!(bank.needs_sync(every));
+    bank.reconcile(&common::bank_details(0, 50), &[]);
+    assert!(!bank.needs_sync(every));
+}
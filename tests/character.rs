@@ -0,0 +1,167 @@
+use artifacts_rs::api::schema::{
+    r#type::{
+        character::{Character, Equipment, EquipmentChange, Skill, SkillProgress},
+        item::{ItemCode, ItemSlot, ItemType},
+    },
+    response::my_characters::{CharacterEquipData, CharacterFightData},
+    SchemaWrapper,
+};
+use std::fs;
+
+fn load_test_data(file_name: &str) -> String {
+    let path = format!("tests/data/my_characters/{}", file_name);
+    fs::read_to_string(path).expect("Failed to read test data file")
+}
+
+fn load_character(file_name: &str) -> Character {
+    let data = load_test_data(file_name);
+    serde_json::from_str::<SchemaWrapper<CharacterEquipData>>(&data)
+        .unwrap()
+        .data
+        .character
+}
+
+#[test]
+fn test_equipment_from_flat_slots() {
+    let character = load_character("character_equip.json");
+    let equipment = &character.equipment;
+
+    assert_eq!(equipment.iter().count(), ItemSlot::ALL.len());
+    assert_eq!(equipment.item_in_slot(&ItemSlot::Weapon), Some("string"));
+    assert_eq!(equipment.item_in_slot(&ItemSlot::Utility1), Some("string"));
+    assert_eq!(equipment.quantity(&ItemSlot::Weapon), 1);
+    assert_eq!(equipment.quantity(&ItemSlot::Utility1), 0);
+    assert!(!equipment.is_slot_empty(&ItemSlot::Utility2));
+}
+
+#[test]
+fn test_equipment_round_trip() {
+    let character = load_character("character_equip.json");
+    let value = serde_json::to_value(&character).unwrap();
+
+    assert_eq!(value["body_armor_slot"], "string");
+    assert_eq!(value["utility2_slot_quantity"], 0);
+    let parsed: Character = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed.equipment, character.equipment);
+}
+
+fn code(code: &str) -> ItemCode {
+    code.parse().unwrap()
+}
+
+#[test]
+fn test_equipment_diff() {
+    let before = Equipment::default();
+    let mut after = before.clone();
+    assert!(before.is_slot_empty(&ItemSlot::Utility1));
+
+    after.set(&ItemSlot::Weapon, code("wooden_stick"), 1);
+    after.set(&ItemSlot::Utility1, code("small_health_potion"), 10);
+
+    assert_eq!(
+        before.diff(&after),
+        vec![
+            EquipmentChange {
+                slot: ItemSlot::Weapon,
+                before: None,
+                before_quantity: 0,
+                after: Some(code("wooden_stick")),
+                after_quantity: 1,
+            },
+            EquipmentChange {
+                slot: ItemSlot::Utility1,
+                before: None,
+                before_quantity: 0,
+                after: Some(code("small_health_potion")),
+                after_quantity: 10,
+            },
+        ]
+    );
+
+    // Using up potions only changes the quantity.
+    let mut used = after.clone();
+    used.set(&ItemSlot::Utility1, code("small_health_potion"), 4);
+    assert_eq!(
+        after.diff(&used),
+        vec![EquipmentChange {
+            slot: ItemSlot::Utility1,
+            before: Some(code("small_health_potion")),
+            before_quantity: 10,
+            after: Some(code("small_health_potion")),
+            after_quantity: 4,
+        }]
+    );
+    assert_eq!(after.quantity(&ItemSlot::Utility1), 10);
+    assert_eq!(after.clear(&ItemSlot::Weapon), Some(code("wooden_stick")));
+    assert_eq!(after.iter().count(), 1);
+}
+
+#[test]
+fn test_skills_from_flat_fields() {
+    let data = fs::read_to_string("tests/data/characters/get_character.json").unwrap();
+    let character = serde_json::from_str::<SchemaWrapper<Character>>(&data)
+        .unwrap()
+        .data;
+
+    assert_eq!(
+        character.skill(Skill::Mining),
+        SkillProgress {
+            level: 12,
+            xp: 100,
+            max_xp: 1000
+        }
+    );
+    assert_eq!(character.skill(Skill::Alchemy).level, 19);
+    assert_eq!(character.skill(Skill::Fighting).max_xp, 500);
+    assert_eq!(character.skills().count(), Skill::ALL.len());
+    assert_eq!(character.skills.iter().count(), Skill::ALL.len() - 1);
+
+    let value = serde_json::to_value(&character).unwrap();
+    assert_eq!(value["mining_level"], 12);
+    assert_eq!(value["alchemy_max_xp"], 1007);
+    assert!(value.get("fighting_level").is_none());
+}
+
+#[test]
+fn test_all_skills() {
+    assert_eq!(Skill::ALL.first(), Some(&Skill::Fishing));
+    assert_eq!(Skill::ALL.last(), Some(&Skill::Fighting));
+    assert!(Skill::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_all_slots() {
+    assert_eq!(ItemSlot::ALL.len(), 14);
+    assert_eq!(ItemSlot::ALL.last(), Some(&ItemSlot::Utility2));
+    assert!(!ItemSlot::ALL.contains(&ItemSlot::Undefined));
+    assert!(ItemSlot::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_task_code() {
+    let character = load_character("character_equip.json");
+    assert_eq!(character.task.task.as_deref(), Some("string"));
+    let value = serde_json::to_value(&character).unwrap();
+    assert_eq!(value["task"], "string");
+
+    // No task is sent as an empty code.
+    let data = fs::read_to_string("tests/data/simulated_fights/chicken.json").unwrap();
+    let fighter = serde_json::from_str::<SchemaWrapper<CharacterFightData>>(&data)
+        .unwrap()
+        .data
+        .character;
+    assert_eq!(fighter.task.task, None);
+    assert_eq!(serde_json::to_value(&fighter).unwrap()["task"], "");
+}
+
+#[test]
+fn test_item_slot() {
+    // Slots are sent in snake case, as in the `*_slot` fields.
+    assert_eq!(
+        serde_json::to_value(ItemSlot::BodyArmor).unwrap(),
+        "body_armor"
+    );
+    assert_eq!(serde_json::to_value(ItemSlot::Ring1).unwrap(), "ring1");
+    assert_eq!(ItemSlot::Ring2.to_item_type(), Some(ItemType::Ring));
+    assert_eq!(ItemSlot::Undefined.to_item_type(), None);
+}
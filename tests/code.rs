@@ -0,0 +1,58 @@
+use artifacts_rs::api::schema::r#type::{
+    code::{InvalidCode, MapCode, MonsterCode, NpcCode},
+    item::{ItemCode, ItemComponent},
+    map::{MapContent, MapContentType},
+};
+use std::collections::HashMap;
+
+#[test]
+fn test_code_validation() {
+    assert!("copper_ore".parse::<ItemCode>().is_ok());
+    assert!("cow-2".parse::<MonsterCode>().is_ok());
+    assert_eq!(
+        "copper ore".parse::<ItemCode>(),
+        Err(InvalidCode {
+            kind: "item",
+            code: "copper ore".to_string()
+        })
+    );
+    assert!("".parse::<ItemCode>().is_err());
+}
+
+#[test]
+fn test_code_serde() {
+    let component: ItemComponent =
+        serde_json::from_str(r#"{"code": "copper_ore", "quantity": 3}"#).unwrap();
+    assert_eq!(component.code, "copper_ore");
+    assert!(
+        serde_json::from_str::<ItemComponent>(r#"{"code": "copper/ore", "quantity": 3}"#).is_err()
+    );
+    assert_eq!(
+        serde_json::to_string(&component).unwrap(),
+        r#"{"code":"copper_ore","quantity":3}"#
+    );
+}
+
+#[test]
+fn test_code_borrow() {
+    let mut bank = HashMap::new();
+    bank.insert(ItemCode::new("copper_ore").unwrap(), 10);
+    assert_eq!(bank.get("copper_ore"), Some(&10));
+
+    let chicken = MonsterCode::new("chicken").unwrap();
+    assert_eq!(MapCode::from(chicken).to_string(), "chicken");
+}
+
+#[test]
+fn test_npc_content() {
+    let merchant = NpcCode::new("fish_merchant").unwrap();
+    let content = MapContent::new(MapContentType::Npc, merchant.clone().into());
+    assert_eq!(content.npc(), Some(merchant));
+    assert_eq!(content.monster(), None);
+    assert_eq!(
+        serde_json::from_str::<MapContent>(r#"{"type": "npc", "code": "fish_merchant"}"#).unwrap(),
+        content
+    );
+    assert_eq!(MapContentType::Npc.to_string(), "npc");
+    assert_eq!(MapContentType::TasksMaster.to_string(), "tasks_master");
+}
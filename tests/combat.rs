@@ -0,0 +1,143 @@
+mod common;
+
+use std::{fs, time::Duration};
+
+use artifacts_rs::{
+    api::schema::{
+        r#type::fight::{FightEvent, FightResult},
+        response::my_characters::CharacterFightData,
+        SchemaWrapper,
+    },
+    combat::{CombatSimulator, Fighter, MAX_TURNS},
+    world::World,
+};
+
+fn load_fight(file_name: &str) -> CharacterFightData {
+    let path = format!("tests/data/simulated_fights/{}", file_name);
+    let data = fs::read_to_string(path).expect("Failed to read test data file");
+    serde_json::from_str::<SchemaWrapper<CharacterFightData>>(&data)
+        .expect("Failed to deserialize test data")
+        .data
+}
+
+/// Rebuild both fighters as they were when a fight started.
+fn replay(world: &World, data: &CharacterFightData, monster: &str) -> (Fighter, Fighter) {
+    let mut character = Fighter::from_character(&data.character, world.items());
+    let events = data.fight.events();
+    if let Some(FightEvent::Start { character_hp, .. }) = events.first() {
+        character.hp = *character_hp;
+    }
+    let potions = events
+        .iter()
+        .filter(|event| matches!(event, FightEvent::Heal { .. }))
+        .count() as u32;
+    if let Some(restore) = character.restores.first_mut() {
+        restore.quantity += potions;
+    }
+    let monster = Fighter::from_monster(world.monster(&monster.parse().unwrap()).unwrap());
+    (character, monster)
+}
+
+/// The fights were written by hand to follow the rules of the simulator, not
+/// recorded from the server: this checks that a fight response is rebuilt
+/// and replayed consistently, not that the rules match the game.
+#[test]
+fn test_replay_fights() {
+    let world = common::load_world();
+    for (file, monster) in [
+        ("chicken.json", "chicken"),
+        ("yellow_slime.json", "yellow_slime"),
+    ] {
+        let data = load_fight(file);
+        let (character, monster) = replay(&world, &data, monster);
+
+        let outcome = CombatSimulator::default().simulate_average(&character, &monster);
+        assert_eq!(outcome.won, data.fight.result == FightResult::Win, "{file}");
+        assert_eq!(outcome.turns as i32, data.fight.turns, "{file}");
+        assert_eq!(outcome.hp, data.character.combat_stats.hp, "{file}");
+        assert_eq!(
+            character.cooldown(outcome.turns),
+            Duration::from_secs(data.cooldown.total_seconds as u64),
+            "{file}"
+        );
+    }
+}
+
+#[test]
+fn test_utilities() {
+    let world = common::load_world();
+    let data = load_fight("yellow_slime.json");
+    let (character, monster) = replay(&world, &data, "yellow_slime");
+    assert_eq!(character.restores.len(), 1);
+    assert_eq!(character.restores[0].hp, 30);
+    assert_eq!(character.restores[0].quantity, 3);
+
+    let outcome = CombatSimulator::default().simulate_average(&character, &monster);
+    assert_eq!(outcome.restores_used, 2);
+
+    let mut without = character.clone();
+    without.restores.clear();
+    let outcome = CombatSimulator::default().simulate_average(&without, &monster);
+    assert!(outcome.won);
+    assert_eq!(outcome.hp, 8);
+    assert_eq!(outcome.restores_used, 0);
+}
+
+#[test]
+fn test_hit_damage() {
+    let world = common::load_world();
+    let slime = Fighter::from_monster(world.monster(&"yellow_slime".parse().unwrap()).unwrap());
+    let mut character = Fighter {
+        hp: 100,
+        max_hp: 100,
+        ..Default::default()
+    };
+    character.attack.earth = 20;
+    character.dmg.earth = 10;
+
+    // 20 × 1.1 = 22, then 22 × (1 − 0.25) = 16.5.
+    assert_eq!(character.hit_damage(&slime).earth, 17);
+    assert_eq!(slime.hit_damage(&character).earth, 8);
+    assert_eq!(slime.hit_damage(&character).total(), 8);
+
+    // Slime blocks 2.5% of earth attacks.
+    assert!((character.expected_damage(&slime) - 17.0 * 0.975).abs() < 1e-9);
+    character.critical_strike = 10;
+    assert!((character.expected_damage(&slime) - 17.0 * 0.975 * 1.05).abs() < 1e-9);
+}
+
+#[test]
+fn test_simulate() {
+    let world = common::load_world();
+    let data = load_fight("chicken.json");
+    let (mut character, chicken) = replay(&world, &data, "chicken");
+    character.critical_strike = 20;
+
+    let simulator = CombatSimulator::new(2000, 7);
+    let prediction = simulator.simulate(&character, &chicken);
+    assert_eq!(prediction, simulator.simulate(&character, &chicken));
+    assert_eq!(prediction.win_probability, 1.0);
+    // Critical strikes can only shorten the fight.
+    assert!(prediction.expected_turns <= 9.0);
+    assert!(prediction.expected_turns > 7.0);
+    assert!(prediction.expected_hp_lost <= 16.0);
+    assert!(prediction.expected_cooldown <= Duration::from_secs(18));
+    assert_eq!(prediction.expected_restores_used, 0.0);
+
+    let weak = Fighter {
+        hp: 10,
+        max_hp: 10,
+        ..character.clone()
+    };
+    let prediction = simulator.simulate(&weak, &chicken);
+    assert_eq!(prediction.win_probability, 0.0);
+    assert_eq!(prediction.expected_hp_lost, 10.0);
+
+    let harmless = Fighter::default();
+    let outcome = simulator.simulate_average(&character, &harmless);
+    assert!(outcome.won);
+    assert_eq!(outcome.turns, 1);
+    let outcome = simulator.simulate_average(&harmless, &chicken);
+    assert!(!outcome.won);
+    assert!(outcome.turns < MAX_TURNS);
+}
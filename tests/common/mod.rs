@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+
+use artifacts_rs::{
+    api::schema::{
+        r#type::{
+            character::{Character, InventoryInfo, InventorySlot, Skill, SkillProgress},
+            item::ItemComponent,
+        },
+        response::my_account::AccountBankDetailsData,
+        PagedSchemaWrapper,
+    },
+    bank::BankState,
+    world::World,
+};
+use serde::de::DeserializeOwned;
+use std::fs;
+
+pub fn load_paged<T: DeserializeOwned>(file_name: &str) -> Vec<T> {
+    let path = format!("tests/data/world/{}", file_name);
+    let data = fs::read_to_string(path).expect("Failed to read test data file");
+    serde_json::from_str::<PagedSchemaWrapper<Vec<T>>>(&data)
+        .expect("Failed to deserialize test data")
+        .data
+}
+
+pub fn load_world() -> World {
+    World::new(
+        load_paged("maps.json"),
+        load_paged("monsters.json"),
+        load_paged("resources.json"),
+        load_paged("items.json"),
+    )
+}
+
+pub fn items(items: &[(&str, u32)]) -> Vec<ItemComponent> {
+    items
+        .iter()
+        .map(|(code, quantity)| ItemComponent {
+            code: code.parse().unwrap(),
+            quantity: *quantity,
+        })
+        .collect()
+}
+
+/// One inventory slot per item, numbered from 1. An empty code makes an
+/// empty slot.
+pub fn slots(items: &[(&str, u32)]) -> Vec<InventorySlot> {
+    items
+        .iter()
+        .zip(1..)
+        .map(|((code, quantity), slot)| InventorySlot {
+            slot,
+            code: code.to_string(),
+            quantity: *quantity,
+        })
+        .collect()
+}
+
+/// A level 1 character at (0, 0) with 120 HP and every skill at level 1,
+/// holding `items` in a 20-slot inventory of 100 items.
+pub fn character(name: &str, items: &[(&str, u32)]) -> Character {
+    let mut inventory = slots(items);
+    inventory.extend(slots(&[("", 0); 20]).into_iter().skip(items.len()));
+    let mut character = Character {
+        name: name.to_string(),
+        inventory_info: InventoryInfo {
+            inventory_max_items: 100,
+            inventory: Some(inventory),
+        },
+        ..Default::default()
+    };
+    character.combat_stats.level = 1;
+    character.combat_stats.hp = 120;
+    character.combat_stats.max_hp = 120;
+    for skill in Skill::ALL {
+        if skill != Skill::Fighting {
+            let progress = SkillProgress {
+                level: 1,
+                xp: 0,
+                max_xp: 150,
+            };
+            character.skills.set(skill, progress);
+        }
+    }
+    character
+}
+
+pub fn bank_details(gold: u32, slots: u32) -> AccountBankDetailsData {
+    AccountBankDetailsData {
+        slots,
+        expansions: 0,
+        next_expansion_cost: 4500,
+        gold,
+    }
+}
+
+/// A bank of 50 slots holding `gold` and `items`.
+pub fn bank(gold: u32, items: &[(&str, u32)]) -> BankState {
+    BankState::new(&bank_details(gold, 50), &self::items(items))
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use artifacts_rs::{
+    api::schema::r#type::{character::Skill, item::ItemComponent},
+    crafting::{CraftingPlanner, Stock},
+};
+use std::collections::BTreeMap;
+
+fn component(code: &str, quantity: u32) -> ItemComponent {
+    ItemComponent {
+        code: code.parse().unwrap(),
+        quantity,
+    }
+}
+
+#[test]
+fn test_recipe_tree() {
+    let world = common::load_world();
+    let planner = CraftingPlanner::new(world.items());
+
+    let tree = planner.tree(&component("wooden_staff", 2)).unwrap();
+    assert_eq!(tree.step.as_ref().unwrap().skill, Skill::Weaponcrafting);
+    assert_eq!(
+        tree.iter()
+            .map(|node| (node.code.to_string(), node.quantity))
+            .collect::<Vec<_>>(),
+        vec![
+            ("wooden_staff".to_string(), 2),
+            ("wooden_stick".to_string(), 2),
+            ("ash_plank".to_string(), 8),
+            ("ash_wood".to_string(), 80),
+        ]
+    );
+}
+
+#[test]
+fn test_crafting_plan_with_stock() {
+    let world = common::load_world();
+    let planner = CraftingPlanner::new(world.items());
+    let stock = Stock::new().with_bank(&[component("copper", 4), component("copper_ore", 25)]);
+
+    let plan = planner
+        .plan(&component("copper_dagger", 2), &stock)
+        .unwrap();
+
+    assert_eq!(
+        plan.raw_materials,
+        BTreeMap::from([("copper_ore".parse().unwrap(), 120)])
+    );
+    // 12 copper needed, 4 in the bank: 8 to craft from 80 ore, 25 in the bank.
+    assert_eq!(
+        plan.missing,
+        BTreeMap::from([("copper_ore".parse().unwrap(), 55)])
+    );
+    assert_eq!(
+        plan.steps
+            .iter()
+            .map(|step| (step.code.to_string(), step.crafts))
+            .collect::<Vec<_>>(),
+        vec![("copper".to_string(), 8), ("copper_dagger".to_string(), 2)]
+    );
+    assert_eq!(
+        plan.required_levels(),
+        BTreeMap::from([(Skill::Mining, 1), (Skill::Weaponcrafting, 1)])
+    );
+    assert!(!plan.is_ready());
+}
+
+#[test]
+fn test_crafting_plan_shared_ingredient() {
+    let world = common::load_world();
+    let planner = CraftingPlanner::new(world.items());
+
+    let plan = planner
+        .plan(&component("feather_coat", 1), &Stock::new())
+        .unwrap();
+    assert_eq!(plan.missing.len(), 2);
+    assert_eq!(plan.missing["ash_wood"], 30);
+    assert_eq!(plan.missing["feather"], 5);
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use artifacts_rs::{
+    action::{
+        ActionOutcome, CharacterAction, Craft, Deposit, Equip, Fight, Gather, Move, Recycle,
+        Withdraw,
+    },
+    api::schema::{
+        r#type::{
+            character::{Character, Position, Skill},
+            item::ItemSlot,
+            map::MapContentType,
+        },
+        response::error_code,
+    },
+    dry_run::{DryRun, DryRunError},
+    runner::{CharacterRunner, RunState},
+};
+
+fn dry_run(character: Character) -> DryRun {
+    let world = Arc::new(common::load_world());
+    DryRun::new(world, [character], common::bank(0, &[("ash_plank", 3)]))
+}
+
+#[test]
+fn test_valid_sequence() {
+    let dry_run = dry_run(common::character("miner", &[("copper_ore", 9)]));
+    let report = dry_run.validate(
+        "miner",
+        &[
+            Move::new(2, 0).into(),
+            Gather.into(),
+            Move::new(1, 2).into(),
+            Craft::new("copper".parse().unwrap(), 1).into(),
+            Move::new(4, 1).into(),
+            Deposit::new("copper".parse().unwrap(), 1).into(),
+            Withdraw::new("ash_plank".parse().unwrap(), 3).into(),
+        ],
+    );
+    assert!(report.is_valid());
+    let seconds: Vec<u64> = report.cooldowns.iter().map(Duration::as_secs).collect();
+    assert_eq!(seconds, [10, 25, 15, 25, 20, 3, 3]);
+    assert_eq!(report.total_cooldown(), Duration::from_secs(101));
+    assert_eq!(dry_run.cooldown("miner"), report.total_cooldown());
+
+    let miner = dry_run.character("miner").unwrap();
+    assert_eq!(miner.position, Position::new(4, 1));
+    assert_eq!(miner.inventory_info.quantity("copper_ore"), 0);
+    assert_eq!(miner.inventory_info.quantity("copper"), 0);
+    assert_eq!(miner.inventory_info.quantity("ash_plank"), 3);
+    let bank = dry_run.bank();
+    assert_eq!(bank.quantity("copper"), 1);
+    assert_eq!(bank.quantity("ash_plank"), 0);
+}
+
+#[test]
+fn test_invalid_steps() {
+    let dry_run = dry_run(common::character(
+        "miner",
+        &[("copper_ore", 5), ("feather", 5)],
+    ));
+
+    // Nothing to gather at the origin.
+    let report = dry_run.validate("miner", &[Gather.into()]);
+    let invalid = report.invalid.unwrap();
+    assert_eq!(invalid.index, 0);
+    assert_eq!(
+        invalid.error,
+        DryRunError::WrongTile {
+            position: Position::new(0, 0),
+            expected: MapContentType::Resource
+        }
+    );
+
+    // Not enough ore for a copper bar.
+    let report = dry_run.validate(
+        "miner",
+        &[
+            Move::new(1, 2).into(),
+            Craft::new("copper".parse().unwrap(), 1).into(),
+            Fight.into(),
+        ],
+    );
+    assert_eq!(report.cooldowns, [Duration::from_secs(15)]);
+    let invalid = report.invalid.unwrap();
+    assert_eq!(invalid.index, 1);
+    assert_eq!(
+        invalid.error,
+        DryRunError::MissingItem {
+            code: "copper_ore".parse().unwrap(),
+            needed: 10,
+            held: 5
+        }
+    );
+    assert_eq!(invalid.error.code(), error_code::MISSING_ITEM);
+
+    // The invalid step left the model untouched.
+    let miner = dry_run.character("miner").unwrap();
+    assert_eq!(miner.position, Position::new(1, 2));
+    assert_eq!(miner.inventory_info.quantity("copper_ore"), 5);
+
+    // Gearcrafting level 5 is needed for a feather coat.
+    let report = dry_run.validate(
+        "miner",
+        &[
+            Move::new(3, 1).into(),
+            Craft::new("feather_coat".parse().unwrap(), 1).into(),
+        ],
+    );
+    assert_eq!(
+        report.invalid.unwrap().error,
+        DryRunError::Level {
+            skill: Skill::Gearcrafting,
+            required: 5,
+            actual: 1
+        }
+    );
+    assert_eq!(
+        dry_run
+            .validate("miner", &[Move::new(3, 1).into()])
+            .invalid
+            .unwrap()
+            .error
+            .code(),
+        error_code::ALREADY_AT_DESTINATION
+    );
+}
+
+#[test]
+fn test_recycle_yields_nothing() {
+    let dry_run = dry_run(common::character("miner", &[("copper", 2)]));
+    let report = dry_run.validate(
+        "miner",
+        &[
+            Move::new(1, 2).into(),
+            Recycle::new("copper".parse().unwrap(), 2).into(),
+        ],
+    );
+    assert!(report.is_valid());
+
+    // Some ore would come back, but how much is random.
+    let miner = dry_run.character("miner").unwrap();
+    assert_eq!(miner.inventory_info.quantity("copper"), 0);
+    assert_eq!(miner.inventory_info.quantity("copper_ore"), 0);
+}
+
+#[test]
+fn test_equip_slot_type() {
+    let dry_run = dry_run(common::character("fighter", &[("copper_dagger", 1)]));
+    let dagger = "copper_dagger".parse().unwrap();
+
+    let report = dry_run.validate("fighter", &[Equip::new(dagger, ItemSlot::Shield).into()]);
+    let error = report.invalid.unwrap().error;
+    assert_eq!(
+        error,
+        DryRunError::WrongSlot {
+            code: "copper_dagger".parse().unwrap(),
+            slot: ItemSlot::Shield
+        }
+    );
+    assert_eq!(error.code(), error_code::INVALID_PAYLOAD);
+    let fighter = dry_run.character("fighter").unwrap();
+    assert_eq!(fighter.inventory_info.quantity("copper_dagger"), 1);
+
+    let dagger = "copper_dagger".parse().unwrap();
+    let report = dry_run.validate("fighter", &[Equip::new(dagger, ItemSlot::Weapon).into()]);
+    assert!(report.is_valid());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dry_run_executor() {
+    let mut fighter = common::character("miner", &[]);
+    fighter.elemental_attributes.attack.water = 10;
+    let dry_run = Arc::new(dry_run(fighter));
+    let (runner, handle, mut results) = CharacterRunner::new("miner", dry_run.clone());
+    handle.extend([
+        Move::new(0, 1).into(),
+        Fight.into(),
+        CharacterAction::from(Deposit::new("raw_chicken".parse().unwrap(), 1)),
+        Move::new(4, 1).into(),
+    ]);
+    let task = tokio::spawn(runner.run());
+
+    let movement = results.recv().await.unwrap();
+    assert_eq!(movement.result.unwrap().character().position.y, 1);
+    let fight = results.recv().await.unwrap().result.unwrap();
+    assert_eq!(fight.character().inventory_info.quantity("raw_chicken"), 1);
+
+    // Deposits need a bank: the runner pauses like on a server error.
+    let deposit = results.recv().await.unwrap();
+    assert_eq!(
+        deposit.result.unwrap_err().error.code,
+        error_code::CONTENT_NOT_FOUND
+    );
+    assert_eq!(handle.state(), RunState::Paused);
+    assert_eq!(handle.pending().len(), 2);
+    assert!(dry_run.cooldown("miner") > Duration::from_secs(5));
+
+    handle.cancel();
+    task.await.unwrap();
+}
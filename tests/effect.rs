@@ -0,0 +1,106 @@
+use artifacts_rs::api::schema::r#type::{
+    character::{Equipment, Skill},
+    effect::{Effect, StatBlock},
+    fight::Element,
+    item::{Item, ItemSlot},
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn item(code: &str, r#type: &str, effects: serde_json::Value) -> Item {
+    serde_json::from_value(json!({
+        "name": code,
+        "code": code,
+        "level": 1,
+        "type": r#type,
+        "subtype": "",
+        "description": "",
+        "effects": effects,
+        "craft": null
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_effect_parsing() {
+    let effects: Vec<Effect> = serde_json::from_value(json!([
+        { "name": "attack_fire", "value": 4 },
+        { "name": "boost_dmg_air", "value": 10 },
+        { "name": "critical_strike", "value": 5 },
+        { "name": "mining", "value": -10 },
+        { "name": "mystery", "value": 1 }
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        effects,
+        vec![
+            Effect::Attack(Element::Fire, 4),
+            Effect::BoostDmg(Element::Air, 10),
+            Effect::CriticalStrike(5),
+            Effect::Gathering(Skill::Mining, -10),
+            Effect::Unknown {
+                name: "mystery".to_string(),
+                value: 1
+            },
+        ]
+    );
+    assert_eq!(
+        serde_json::to_value(&effects).unwrap()[1],
+        json!({ "name": "boost_dmg_air", "value": 10 })
+    );
+    assert_ne!(Effect::Hp(10), Effect::Hp(20));
+}
+
+#[test]
+fn test_stat_block_from_equipment() {
+    let sword = item(
+        "copper_dagger",
+        "weapon",
+        json!([{ "name": "attack_fire", "value": 6 }, { "name": "critical_strike", "value": 35 }]),
+    );
+    let ring = item(
+        "copper_ring",
+        "ring",
+        json!([{ "name": "dmg_fire", "value": 5 }, { "name": "hp", "value": 10 }]),
+    );
+    let items: HashMap<_, _> = [sword, ring]
+        .into_iter()
+        .map(|item| (item.code.clone(), item))
+        .collect();
+
+    let mut equipment = Equipment::default();
+    equipment.set(&ItemSlot::Weapon, "copper_dagger".parse().unwrap(), 1);
+    equipment.set(&ItemSlot::Ring1, "copper_ring".parse().unwrap(), 1);
+    equipment.set(&ItemSlot::Ring2, "copper_ring".parse().unwrap(), 1);
+
+    let stats = StatBlock::from_equipment(&equipment, &items);
+    assert_eq!(stats.attack[Element::Fire], 6);
+    assert_eq!(stats.dmg[Element::Fire], 10);
+    assert_eq!(stats.hp, 20);
+    assert_eq!(stats.critical_strike, 35);
+}
+
+#[test]
+fn test_stat_block_keeps_boosts_apart() {
+    let effects: Vec<Effect> = serde_json::from_value(json!([
+        { "name": "hp", "value": 10 },
+        { "name": "boost_hp", "value": 30 },
+        { "name": "res_water", "value": 5 },
+        { "name": "boost_res_water", "value": 10 },
+        { "name": "boost_dmg_earth", "value": 12 },
+        { "name": "heal", "value": 50 },
+        { "name": "restore", "value": 20 }
+    ]))
+    .unwrap();
+
+    let stats: StatBlock = effects.iter().collect();
+    assert_eq!(stats.hp, 10);
+    assert_eq!(stats.boost_hp, 30);
+    assert_eq!(stats.res[Element::Water], 5);
+    assert_eq!(stats.boost_res[Element::Water], 10);
+    assert_eq!(stats.dmg, Default::default());
+    assert_eq!(stats.boost_dmg[Element::Earth], 12);
+    assert_eq!(stats.heal, 50);
+    assert_eq!(stats.restore, 20);
+}
@@ -0,0 +1,63 @@
+use artifacts_rs::api::schema::r#type::{
+    elemental::ElementalStats, fight::Element, monster::Monster,
+};
+use serde_json::json;
+
+fn chicken() -> serde_json::Value {
+    json!({
+        "name": "Chicken",
+        "code": "chicken",
+        "level": 1,
+        "hp": 60,
+        "attack_fire": 0,
+        "attack_earth": 0,
+        "attack_water": 4,
+        "attack_air": 0,
+        "res_fire": 0,
+        "res_earth": 0,
+        "res_water": 0,
+        "res_air": 0,
+        "min_gold": 0,
+        "max_gold": 3,
+        "drops": [
+            { "code": "raw_chicken", "rate": 1, "min_quantity": 1, "max_quantity": 1 }
+        ]
+    })
+}
+
+#[test]
+fn test_monster_elemental_stats() {
+    let monster: Monster = serde_json::from_value(chicken()).unwrap();
+    assert_eq!(monster.attack[Element::Water], 4);
+    assert_eq!(monster.attack.total(), 4);
+    assert_eq!(monster.res, ElementalStats::default());
+    assert_eq!(serde_json::to_value(&monster).unwrap(), chicken());
+}
+
+#[test]
+fn test_elemental_stats_arithmetic() {
+    let a = ElementalStats {
+        fire: 1,
+        earth: 2,
+        water: 3,
+        air: 4,
+    };
+    let b = ElementalStats::from_fn(|_| 10);
+
+    assert_eq!((a + b).fire, 11);
+    assert_eq!((b - a).air, 6);
+    assert_eq!((a * 2).water, 6);
+    assert_eq!(
+        a.iter().map(|(element, _)| element).collect::<Vec<_>>(),
+        Element::ALL
+    );
+    assert_eq!(*a.get(Element::Earth), 2);
+}
+
+#[test]
+fn test_missing_elemental_field() {
+    let mut chicken = chicken();
+    chicken.as_object_mut().unwrap().remove("res_air");
+    let error = serde_json::from_value::<Monster>(chicken).unwrap_err();
+    assert!(error.to_string().contains("missing field `res_air`"));
+}
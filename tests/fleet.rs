@@ -0,0 +1,189 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use artifacts_rs::{
+    action::{CharacterAction, Rest, Withdraw},
+    api::schema::{
+        r#type::{character::Character, item::ItemComponent},
+        response::{
+            error_code::MISSING_ITEM,
+            my_characters::{CharacterItemTransactionData, CharacterRestData},
+            ResponseData, ResponseError,
+        },
+        SchemaWrapper,
+    },
+    fleet::{Fleet, RateLimit},
+    runner::Executor,
+};
+use serde::de::DeserializeOwned;
+use tokio::time::Instant;
+
+fn load<T: DeserializeOwned>(file_name: &str) -> T {
+    let path = format!("tests/data/my_characters/{}", file_name);
+    let data = fs::read_to_string(path).expect("Failed to read test data file");
+    serde_json::from_str::<SchemaWrapper<T>>(&data)
+        .expect("Failed to deserialize test data")
+        .data
+}
+
+/// A server with a bank, where each request takes one second.
+#[derive(Clone, Default)]
+struct Server {
+    bank: Arc<Mutex<BTreeMap<String, u32>>>,
+    calls: Arc<Mutex<Vec<(String, Instant)>>>,
+}
+
+impl Executor for Server {
+    async fn execute(
+        &self,
+        name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((name.to_string(), Instant::now()));
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        match action {
+            CharacterAction::Rest(_) => {
+                let mut data: CharacterRestData = load("character_rest.json");
+                data.character.name = name.to_string();
+                Ok(data.into())
+            }
+            CharacterAction::Withdraw(withdraw) => {
+                let mut bank = self.bank.lock().unwrap();
+                let stored = bank.entry(withdraw.code.to_string()).or_default();
+                if *stored < withdraw.quantity {
+                    return Err(ResponseError::new(MISSING_ITEM, "Missing item.".into()));
+                }
+                *stored -= withdraw.quantity;
+                let mut data: CharacterItemTransactionData =
+                    load("character_withdraw_bank_item.json");
+                data.character.name = name.to_string();
+                data.bank = bank
+                    .iter()
+                    .map(|(code, quantity)| ItemComponent {
+                        code: code.parse().unwrap(),
+                        quantity: *quantity,
+                    })
+                    .collect();
+                Ok(data.into())
+            }
+            _ => unimplemented!(),
+        }
+    }
+}
+
+fn characters(names: &[&str]) -> Vec<Character> {
+    names
+        .iter()
+        .map(|name| common::character(name, &[]))
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_fleet_bank_coordination() {
+    let server = Server::default();
+    server
+        .bank
+        .lock()
+        .unwrap()
+        .insert("iron_bar".to_string(), 10);
+    let limit = RateLimit::new(10, Duration::from_secs(1));
+    let mut fleet = Fleet::new(
+        server.clone(),
+        limit,
+        characters(&["alice", "bob"]),
+        common::bank(0, &[("iron_bar", 10)]),
+    );
+    assert_eq!(fleet.names().collect::<Vec<_>>(), ["alice", "bob"]);
+
+    let code = "iron_bar".parse().unwrap();
+    for name in ["alice", "bob"] {
+        fleet
+            .handle(name)
+            .unwrap()
+            .push(Withdraw::new("iron_bar".parse().unwrap(), 10));
+    }
+
+    let first = fleet.next_event().await.unwrap();
+    let second = fleet.next_event().await.unwrap();
+    // Bob is refused locally, before Alice's withdrawal completes.
+    assert_eq!(second.name, "alice");
+    assert_eq!(first.name, "bob");
+    assert_eq!(first.result.unwrap_err().error.code, MISSING_ITEM);
+    assert!(second.result.is_ok());
+    assert_eq!(second.character().unwrap().name, "alice");
+    assert_eq!(server.calls.lock().unwrap().len(), 1);
+    assert_eq!(fleet.bank_quantity(&code), 0);
+    assert_eq!(fleet.bank().quantity("iron_bar"), 0);
+
+    fleet.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_fleet_rate_limit() {
+    let server = Server::default();
+    let names = ["alice", "bob", "carol"];
+    let limit = RateLimit::new(2, Duration::from_secs(1));
+    let mut fleet = Fleet::new(
+        server.clone(),
+        limit,
+        characters(&names),
+        common::bank(0, &[]),
+    );
+
+    let start = Instant::now();
+    for name in names {
+        let handle = fleet.handle(name).unwrap();
+        handle.push(Rest);
+        handle.push(Rest);
+    }
+    let mut events = Vec::new();
+    for _ in 0..6 {
+        events.push(fleet.next_event().await.unwrap());
+    }
+    assert!(events.iter().all(|event| event.result.is_ok()));
+    assert_eq!(fleet.characters().len(), 3);
+
+    // No more than two requests start in any second.
+    let calls = server.calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 6);
+    for window in calls.windows(3) {
+        assert!(window[2].1 - window[0].1 >= Duration::from_secs(1));
+    }
+    assert!(calls[5].1 - start >= Duration::from_secs(2));
+
+    fleet.shutdown().await;
+}
+
+/// A new fleet waits out the cooldown the server reported on its characters
+/// before their first action.
+#[tokio::test(start_paused = true)]
+async fn test_fleet_waits_initial_cooldown() {
+    let server = Server::default();
+    let mut characters = characters(&["alice"]);
+    let cooldown = &mut characters[0].cooldown_info;
+    cooldown.cooldown = 30;
+    #[cfg(feature = "chrono")]
+    let expiration = (chrono::Utc::now() + chrono::TimeDelta::seconds(30)).to_rfc3339();
+    // Without `chrono` the expiration is not read, the whole cooldown is.
+    #[cfg(not(feature = "chrono"))]
+    let expiration = "2999-01-01T00:00:00Z".to_string();
+    cooldown.cooldown_expiration = Some(expiration.into());
+    let limit = RateLimit::new(10, Duration::from_secs(1));
+    let mut fleet = Fleet::new(server, limit, characters, common::bank(0, &[]));
+
+    let start = Instant::now();
+    fleet.handle("alice").unwrap().push(Rest);
+    assert!(fleet.next_event().await.unwrap().result.is_ok());
+    assert!(start.elapsed() >= Duration::from_secs(29));
+
+    fleet.shutdown().await;
+}
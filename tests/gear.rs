@@ -0,0 +1,92 @@
+mod common;
+
+use artifacts_rs::{
+    action::{Equip, Unequip},
+    api::schema::r#type::{
+        character::{Character, Skill},
+        item::{ItemCode, ItemComponent, ItemSlot},
+    },
+    combat::CombatSimulator,
+    gear::{GearOptimizer, Objective},
+};
+
+fn code(code: &str) -> ItemCode {
+    code.parse().unwrap()
+}
+
+/// A level 1 character holding a wooden stick, with a dagger and gloves in
+/// its inventory.
+fn fighter() -> Character {
+    let held = [
+        ("copper_dagger", 1),
+        ("apprentice_gloves", 1),
+        ("copper_ring", 1),
+    ];
+    let mut character = common::character("", &held);
+    character.combat_stats.hp = 100;
+    character.combat_stats.max_hp = 100;
+    character.elemental_attributes.attack.earth = 4;
+    character
+        .equipment
+        .set(&ItemSlot::Weapon, code("wooden_stick"), 1);
+    character
+}
+
+fn bank() -> Vec<ItemComponent> {
+    common::items(&[("wooden_staff", 1), ("feather_coat", 1), ("copper_ring", 1)])
+}
+
+#[test]
+fn test_optimize_fight() {
+    let world = common::load_world();
+    let optimizer = GearOptimizer::new(world.items()).with_simulator(CombatSimulator::new(200, 1));
+    let slime = world.monster(&"yellow_slime".parse().unwrap()).unwrap();
+
+    let plan = optimizer.optimize(&fighter(), &bank(), Objective::Fight(slime));
+    assert_eq!(
+        plan.equipment.get(&ItemSlot::Weapon),
+        Some(&code("wooden_staff"))
+    );
+    // The coat is level 5.
+    assert!(plan.equipment.is_slot_empty(&ItemSlot::BodyArmor));
+    assert!(plan.withdraw.contains(&ItemComponent {
+        code: code("wooden_staff"),
+        quantity: 1,
+    }));
+    assert!(plan
+        .withdraw
+        .iter()
+        .all(|item| item.code != "feather_coat" && item.quantity == 1));
+    assert_eq!(
+        plan.actions[..2],
+        [
+            Unequip::new(ItemSlot::Weapon).into(),
+            Equip::new(code("wooden_staff"), ItemSlot::Weapon).into(),
+        ]
+    );
+}
+
+#[test]
+fn test_optimize_gathering() {
+    let world = common::load_world();
+    let optimizer = GearOptimizer::new(world.items());
+    let mut character = fighter();
+    character
+        .equipment
+        .set(&ItemSlot::Utility1, code("small_health_potion"), 5);
+
+    let plan = optimizer.optimize(&character, &bank(), Objective::Gathering(Skill::Mining));
+    assert_eq!(
+        plan.actions,
+        [
+            Unequip::new(ItemSlot::Weapon).into(),
+            Equip::new(code("apprentice_gloves"), ItemSlot::Weapon).into(),
+        ]
+    );
+    assert!(plan.withdraw.is_empty());
+    assert_eq!(plan.equipment.quantity(&ItemSlot::Utility1), 5);
+
+    character.equipment = plan.equipment;
+    let plan = optimizer.optimize(&character, &bank(), Objective::Gathering(Skill::Mining));
+    assert!(plan.is_empty());
+}
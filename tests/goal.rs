@@ -0,0 +1,212 @@
+mod common;
+
+use artifacts_rs::{
+    action::{CharacterAction, Craft, Deposit, Equip, Gather, Move, Unequip, Withdraw},
+    api::schema::{
+        r#type::{
+            character::Skill,
+            item::{ItemCode, ItemSlot},
+        },
+        response::{error_code, ResponseError},
+    },
+    goal::{Goal, GoalPlanner, PlanError},
+};
+
+fn code(code: &str) -> ItemCode {
+    code.parse().unwrap()
+}
+
+fn gathers(count: usize) -> impl Iterator<Item = CharacterAction> {
+    std::iter::repeat_n(Gather.into(), count)
+}
+
+#[test]
+fn test_plan_bank_item() {
+    let world = common::load_world();
+    let planner = GoalPlanner::new(&world);
+    let goal = Goal::BankItem {
+        code: code("copper_dagger"),
+        quantity: 1,
+    };
+
+    // From nothing: mine the ore, smelt it, craft the dagger, store it.
+    let plan = planner
+        .plan(&goal, &common::character("", &[]), &[])
+        .unwrap();
+    let mut expected: Vec<CharacterAction> = vec![Move::new(2, 0).into()];
+    expected.extend(gathers(60));
+    expected.extend([
+        Move::new(1, 2).into(),
+        Craft::new(code("copper"), 6).into(),
+        Move::new(1, 5).into(),
+        Craft::new(code("copper_dagger"), 1).into(),
+        Move::new(7, 5).into(),
+        Deposit::new(code("copper_dagger"), 1).into(),
+    ]);
+    assert_eq!(plan.actions, expected);
+
+    // Bars in the bank are withdrawn instead, and stored daggers count.
+    let goal = Goal::BankItem {
+        code: code("copper_dagger"),
+        quantity: 2,
+    };
+    let bank = common::items(&[("copper", 6), ("copper_dagger", 1)]);
+    let plan = planner
+        .plan(&goal, &common::character("", &[]), &bank)
+        .unwrap();
+    assert_eq!(
+        plan.actions,
+        [
+            Move::new(4, 1).into(),
+            Withdraw::new(code("copper"), 6).into(),
+            Move::new(1, 5).into(),
+            Craft::new(code("copper_dagger"), 1).into(),
+            Move::new(7, 5).into(),
+            Deposit::new(code("copper_dagger"), 1).into(),
+        ]
+    );
+
+    let bank = common::items(&[("copper_dagger", 2)]);
+    assert!(planner
+        .plan(&goal, &common::character("", &[]), &bank)
+        .unwrap()
+        .is_done());
+}
+
+#[test]
+fn test_plan_equip() {
+    let world = common::load_world();
+    let planner = GoalPlanner::new(&world);
+    let bank = common::items(&[("copper_dagger", 1)]);
+    let mut character = common::character("", &[]);
+    character
+        .equipment
+        .set(&ItemSlot::Weapon, code("wooden_stick"), 1);
+
+    let goal = Goal::Equip {
+        code: code("copper_dagger"),
+    };
+    let plan = planner.plan(&goal, &character, &bank).unwrap();
+    assert_eq!(
+        plan.actions,
+        [
+            Move::new(4, 1).into(),
+            Withdraw::new(code("copper_dagger"), 1).into(),
+            Unequip::new(ItemSlot::Weapon).into(),
+            Equip::new(code("copper_dagger"), ItemSlot::Weapon).into(),
+        ]
+    );
+
+    let goal = Goal::Equip {
+        code: code("wooden_stick"),
+    };
+    assert!(planner.plan(&goal, &character, &bank).unwrap().is_done());
+
+    let goal = Goal::Equip {
+        code: code("feather_coat"),
+    };
+    assert_eq!(
+        planner.plan(&goal, &character, &bank),
+        Err(PlanError::Level {
+            skill: Skill::Fighting,
+            level: 5
+        })
+    );
+}
+
+#[test]
+fn test_plan_level() {
+    let world = common::load_world();
+    let planner = GoalPlanner::new(&world);
+    let goal = Goal::Level {
+        skill: Skill::Mining,
+        level: 2,
+    };
+
+    // 150 XP at an estimated 12 XP per copper rocks.
+    let plan = planner
+        .plan(&goal, &common::character("", &[]), &[])
+        .unwrap();
+    let mut expected: Vec<CharacterAction> = vec![Move::new(2, 0).into()];
+    expected.extend(gathers(13));
+    assert_eq!(plan.actions, expected);
+}
+
+#[test]
+fn test_replan() {
+    let world = common::load_world();
+    let planner = GoalPlanner::new(&world);
+    let goal = Goal::BankItem {
+        code: code("copper"),
+        quantity: 1,
+    };
+    let mut character = common::character("", &[("copper_ore", 4)]);
+    character.position.x = 2;
+
+    // The ore goes to the bank before the plan resumes from there.
+    let full = ResponseError::new(
+        error_code::INVENTORY_FULL,
+        "Character inventory is full.".into(),
+    );
+    let plan = planner.replan(&goal, &character, &[], &full).unwrap();
+    let mut expected: Vec<CharacterAction> = vec![
+        Move::new(4, 1).into(),
+        Deposit::new(code("copper_ore"), 4).into(),
+        Withdraw::new(code("copper_ore"), 4).into(),
+        Move::new(2, 0).into(),
+    ];
+    expected.extend(gathers(6));
+    expected.extend([
+        Move::new(1, 2).into(),
+        Craft::new(code("copper"), 1).into(),
+        Move::new(4, 1).into(),
+        Deposit::new(code("copper"), 1).into(),
+    ]);
+    assert_eq!(plan.actions, expected);
+
+    let missing = ResponseError::new(
+        error_code::MISSING_ITEM,
+        "Missing item or insufficient quantity.".into(),
+    );
+    assert_eq!(
+        planner.replan(&goal, &character, &[], &missing),
+        planner.plan(&goal, &character, &[])
+    );
+
+    let not_found = ResponseError::new(
+        error_code::CHARACTER_NOT_FOUND,
+        "Character not found.".into(),
+    );
+    assert!(matches!(
+        planner.replan(&goal, &character, &[], &not_found),
+        Err(PlanError::Failed {
+            code: error_code::CHARACTER_NOT_FOUND,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_replan_deposit_order() {
+    let world = common::load_world();
+    let planner = GoalPlanner::new(&world);
+    let goal = Goal::BankItem {
+        code: code("copper"),
+        quantity: 1,
+    };
+    let character = common::character("", &[("feather", 2), ("copper_ore", 4), ("ash_wood", 3)]);
+
+    // A full inventory is deposited by item code, whatever the slots.
+    let full = ResponseError::new(
+        error_code::INVENTORY_FULL,
+        "Character inventory is full.".into(),
+    );
+    let plan = planner.replan(&goal, &character, &[], &full).unwrap();
+    let expected: [CharacterAction; 4] = [
+        Move::new(4, 1).into(),
+        Deposit::new(code("ash_wood"), 3).into(),
+        Deposit::new(code("copper_ore"), 4).into(),
+        Deposit::new(code("feather"), 2).into(),
+    ];
+    assert_eq!(plan.actions[..4], expected);
+}
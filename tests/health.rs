@@ -0,0 +1,125 @@
+mod common;
+
+use std::time::Duration;
+
+use artifacts_rs::{
+    action::{CharacterAction, Rest, UseItem},
+    api::schema::r#type::{character::Character, effect::Effect, item::ItemComponent},
+    health::{rest_time, HpPolicy, Recovery},
+};
+
+/// A character hitting yellow slimes for 10 water damage, taking 8 earth
+/// damage per turn: it needs 49 HP to survive the 6 hits before the slime
+/// dies.
+fn fighter(hp: i32, max_hp: i32, food: &[(&str, u32)]) -> Character {
+    let mut character = common::character("fighter", food);
+    character.combat_stats.level = 2;
+    character.combat_stats.hp = hp;
+    character.combat_stats.max_hp = max_hp;
+    character.elemental_attributes.attack.water = 10;
+    character
+}
+
+fn food(code: &str, quantity: u32) -> ItemComponent {
+    ItemComponent {
+        code: code.parse().unwrap(),
+        quantity,
+    }
+}
+
+#[test]
+fn test_rest_time() {
+    assert_eq!(rest_time(30, 120), Duration::from_secs(18));
+    assert_eq!(rest_time(118, 120), Duration::from_secs(3));
+    assert_eq!(rest_time(120, 120), Duration::from_secs(3));
+}
+
+#[test]
+fn test_required_hp() {
+    let world = common::load_world();
+    let policy = HpPolicy::new(world.items());
+    let slime = world.monster(&"yellow_slime".parse().unwrap()).unwrap();
+
+    assert_eq!(policy.required_hp(&fighter(1, 120, &[]), slime), Some(49));
+    assert_eq!(policy.required_hp(&fighter(1, 48, &[]), slime), None);
+}
+
+#[test]
+fn test_before_fight() {
+    let world = common::load_world();
+    let policy = HpPolicy::new(world.items());
+    let slime = world.monster(&"yellow_slime".parse().unwrap()).unwrap();
+
+    assert_eq!(
+        policy.before_fight(&fighter(110, 120, &[]), slime),
+        Recovery::Ready
+    );
+    assert_eq!(
+        policy.before_fight(&fighter(40, 48, &[]), slime),
+        Recovery::Unwinnable
+    );
+
+    // Without food the character rests.
+    let recovery = policy.before_fight(&fighter(30, 120, &[]), slime);
+    assert_eq!(
+        recovery,
+        Recovery::Rest {
+            duration: Duration::from_secs(18)
+        }
+    );
+    assert_eq!(recovery.actions(), [CharacterAction::from(Rest)]);
+
+    // An apple is quicker than an 18 s rest, and wastes less than a chicken.
+    let recovery = policy.before_fight(
+        &fighter(30, 120, &[("cooked_chicken", 3), ("apple", 2)]),
+        slime,
+    );
+    assert_eq!(
+        recovery,
+        Recovery::Eat {
+            items: vec![food("apple", 1)],
+            heal: 50,
+            duration: Duration::from_secs(3),
+        }
+    );
+    assert_eq!(
+        recovery.actions(),
+        [CharacterAction::from(UseItem::new(
+            "apple".parse().unwrap(),
+            1
+        ))]
+    );
+
+    // Resting is as quick as eating, so the food is kept.
+    assert_eq!(
+        policy.before_fight(&fighter(45, 60, &[("apple", 1)]), slime),
+        Recovery::Rest {
+            duration: Duration::from_secs(3)
+        }
+    );
+}
+
+#[test]
+fn test_combined_meal() {
+    let world = common::load_world();
+    let mut items = world.items().clone();
+    for (code, heal) in [("apple", 20), ("cooked_gudgeon", 15)] {
+        items.get_mut(code).unwrap().effects = vec![Effect::Heal(heal)];
+    }
+    let policy = HpPolicy::new(&items);
+    let slime = world.monster(&"yellow_slime".parse().unwrap()).unwrap();
+
+    // No single food heals the 48 HP missing.
+    let recovery = policy.before_fight(
+        &fighter(1, 200, &[("apple", 2), ("cooked_gudgeon", 1)]),
+        slime,
+    );
+    assert_eq!(
+        recovery,
+        Recovery::Eat {
+            items: vec![food("apple", 2), food("cooked_gudgeon", 1)],
+            heal: 55,
+            duration: Duration::from_secs(6),
+        }
+    );
+}
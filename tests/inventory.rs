@@ -0,0 +1,160 @@
+mod common;
+
+use std::{
+    fs,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use artifacts_rs::{
+    action::{CharacterAction, Craft, Deposit, Gather, Move},
+    api::schema::{
+        r#type::character::{Character, Position},
+        response::{my_characters::CharacterRestData, ResponseData, ResponseError},
+        SchemaWrapper,
+    },
+    inventory::{BankTripPolicy, BankTrips, InventoryChange},
+    runner::{CharacterRunner, Executor},
+};
+
+/// A miner on the copper rocks holding ore, wood and an optional third item,
+/// in three slots.
+fn miner(max_items: u32, third: Option<(&str, u32)>) -> Character {
+    let mut character = common::character("miner", &[]);
+    character.position = Position::new(2, 0);
+    character.inventory_info.inventory_max_items = max_items;
+    let third = third.unwrap_or(("", 0));
+    let slots = common::slots(&[("copper_ore", 90), ("ash_wood", 5), third]);
+    character.inventory_info.inventory = Some(slots);
+    character
+}
+
+#[test]
+fn test_capacity() {
+    let inventory = miner(100, None).inventory_info;
+    assert_eq!(inventory.total_items(), 95);
+    assert_eq!(inventory.free_capacity(), 5);
+    assert_eq!(inventory.free_slots(), 1);
+
+    let inventory = miner(90, Some(("apple", 1))).inventory_info;
+    assert_eq!(inventory.free_capacity(), 0);
+    assert_eq!(inventory.free_slots(), 0);
+}
+
+#[test]
+fn test_predict() {
+    let world = common::load_world();
+    let gather = InventoryChange::predict(&world, &miner(100, None), &Gather.into());
+    assert_eq!(
+        gather.added.keys().map(|code| &**code).collect::<Vec<_>>(),
+        ["copper_ore", "topaz_stone"]
+    );
+    assert!(gather.removed.is_empty());
+    assert!(gather.fits(&miner(100, None).inventory_info));
+    // No room for a second item.
+    assert!(!gather.fits(&miner(96, None).inventory_info));
+    // No slot for the topaz.
+    assert!(!gather.fits(&miner(200, Some(("apple", 1))).inventory_info));
+
+    // Using up all the ore frees its slot for the bars.
+    let craft = Craft::new("copper".parse().unwrap(), 9).into();
+    let change = InventoryChange::predict(&world, &miner(96, None), &craft);
+    assert_eq!(change.removed.values().sum::<u32>(), 90);
+    assert_eq!(change.added.values().sum::<u32>(), 9);
+    assert!(change.fits(&miner(96, Some(("apple", 1))).inventory_info));
+
+    let craft = Craft::new("copper".parse().unwrap(), 8).into();
+    let change = InventoryChange::predict(&world, &miner(96, None), &craft);
+    assert!(!change.fits(&miner(96, Some(("apple", 1))).inventory_info));
+}
+
+#[test]
+fn test_bank_trip_policy() {
+    let world = common::load_world();
+    let policy = BankTripPolicy::new(&world);
+    assert!(policy.before(&miner(100, None), &Gather.into()).is_empty());
+
+    assert_eq!(
+        policy.before(&miner(96, None), &Gather.into()),
+        [
+            Move::new(4, 1).into(),
+            Deposit::new("ash_wood".parse().unwrap(), 5).into(),
+            Deposit::new("copper_ore".parse().unwrap(), 90).into(),
+            Move::new(2, 0).into(),
+        ]
+    );
+
+    // Materials of a craft stay in the inventory.
+    let craft = Craft::new("copper".parse().unwrap(), 8).into();
+    assert_eq!(
+        policy.before(&miner(96, Some(("apple", 1))), &craft),
+        [
+            Move::new(4, 1).into(),
+            Deposit::new("apple".parse().unwrap(), 1).into(),
+            Deposit::new("ash_wood".parse().unwrap(), 5).into(),
+            Deposit::new("copper_ore".parse().unwrap(), 10).into(),
+            Move::new(2, 0).into(),
+        ]
+    );
+}
+
+/// Records the actions sent and answers with a recorded rest, each request
+/// taking one second.
+#[derive(Default)]
+struct Recorder {
+    sent: Mutex<Vec<CharacterAction>>,
+}
+
+impl Executor for Recorder {
+    async fn execute(
+        &self,
+        _name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        self.sent.lock().unwrap().push(action.clone());
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let data = fs::read_to_string("tests/data/my_characters/character_rest.json")
+            .expect("Failed to read test data file");
+        let data = serde_json::from_str::<SchemaWrapper<CharacterRestData>>(&data)
+            .expect("Failed to deserialize test data")
+            .data;
+        Ok(data.into())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_bank_trips() {
+    let executor = BankTrips::new(Recorder::default(), Arc::new(common::load_world()));
+    executor.observe(&miner(96, None));
+    assert_eq!(executor.before("miner", &Gather.into()).len(), 4);
+    // Characters never observed are left alone.
+    assert!(executor.before("other", &Gather.into()).is_empty());
+
+    // The runner queues the trip ahead of the gather, one action at a time.
+    let executor = Arc::new(executor);
+    let (runner, handle, mut results) = CharacterRunner::new("miner", executor.clone());
+    handle.push(Gather);
+    let task = tokio::spawn(runner.run());
+
+    let first = results.recv().await.unwrap();
+    assert_eq!(first.action, Move::new(4, 1).into());
+    handle.pause();
+    let checkpoint = handle.checkpoint();
+    assert!(matches!(
+        checkpoint.in_flight,
+        Some(CharacterAction::Deposit(_))
+    ));
+    assert_eq!(checkpoint.pending.len(), 3);
+    assert_eq!(checkpoint.pending.last(), Some(&Gather.into()));
+
+    handle.resume();
+    for _ in 0..4 {
+        assert!(results.recv().await.unwrap().result.is_ok());
+    }
+    let sent = executor.inner().sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 5);
+    assert_eq!(sent.last(), Some(&Gather.into()));
+
+    handle.cancel();
+    task.await.unwrap();
+}
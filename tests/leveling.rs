@@ -0,0 +1,162 @@
+mod common;
+
+use std::time::Duration;
+
+use artifacts_rs::{
+    api::schema::r#type::character::{Character, Skill, SkillProgress},
+    combat::CombatSimulator,
+    leveling::{Activity, LevelingError, LevelingPlanner, DEFAULT_ACTION_SECONDS},
+};
+
+fn fighter() -> Character {
+    let mut character = common::character("", &[]);
+    character.combat_stats.max_xp = 150;
+    character.elemental_attributes.attack.water = 10;
+    character.elemental_attributes.dmg.water = 20;
+    character
+}
+
+#[test]
+fn test_plan_gathering() {
+    let world = common::load_world();
+    let mut planner = LevelingPlanner::new(&world);
+    let character = fighter();
+    planner.observe(&character);
+    assert_eq!(planner.xp_to_next(Skill::Mining, 1), Some(150));
+    assert_eq!(planner.xp_to_next(Skill::Mining, 2), None);
+
+    // Copper rocks are estimated at 12 XP per gather, and level 2 at the
+    // 150 XP of level 1.
+    let plan = planner.plan(&character, Skill::Mining, 3).unwrap();
+    let copper_rocks = Activity::Gather("copper_rocks".parse().unwrap());
+    assert_eq!(plan.steps.len(), 2);
+    assert!(plan.steps.iter().all(|step| step.activity == copper_rocks));
+    assert!(plan.steps.iter().all(|step| step.estimated));
+    assert_eq!(plan.steps[0].actions, 13);
+    assert_eq!(plan.steps[1].actions, 12);
+    assert_eq!(plan.actions, 25);
+    assert_eq!(
+        plan.duration,
+        Duration::from_secs(25 * u64::from(DEFAULT_ACTION_SECONDS))
+    );
+
+    // Measured XP and cooldown replace the estimates.
+    planner.record(copper_rocks.clone(), 2, 60, 40);
+    assert_eq!(
+        planner.observed(&copper_rocks).unwrap().xp_per_action(),
+        30.0
+    );
+    let plan = planner.plan(&character, Skill::Mining, 3).unwrap();
+    assert_eq!(plan.actions, 5 + 5);
+    assert_eq!(plan.duration, Duration::from_secs(10 * 20));
+    assert!(!plan.steps[0].estimated);
+    assert!(plan.steps[1].estimated);
+
+    // So does the XP a character needs at level 2.
+    let mut miner = fighter();
+    miner.skills.set(
+        Skill::Mining,
+        SkillProgress {
+            level: 2,
+            max_xp: 165,
+            ..Default::default()
+        },
+    );
+    planner.observe(&miner);
+    assert_eq!(planner.xp_to_next(Skill::Mining, 2), Some(165));
+    let plan = planner.plan(&character, Skill::Mining, 3).unwrap();
+    assert_eq!(plan.actions, 5 + 6);
+    assert!(!plan.steps[1].estimated);
+
+    assert!(planner
+        .plan(&character, Skill::Mining, 1)
+        .unwrap()
+        .steps
+        .is_empty());
+}
+
+#[test]
+fn test_plan_crafting() {
+    let world = common::load_world();
+    let planner = LevelingPlanner::new(&world);
+    let plan = planner.plan(&fighter(), Skill::Weaponcrafting, 2).unwrap();
+
+    // The staff needs a stick, which cannot be obtained.
+    let step = &plan.steps[0];
+    assert_eq!(
+        step.activity,
+        Activity::Craft("copper_dagger".parse().unwrap())
+    );
+    assert!(step.material_actions > step.actions);
+    assert_eq!(plan.actions, step.actions + step.material_actions);
+}
+
+#[test]
+fn test_plan_fighting() {
+    let world = common::load_world();
+    let planner = LevelingPlanner::new(&world).with_simulator(CombatSimulator::new(200, 1));
+    let plan = planner.plan(&fighter(), Skill::Fighting, 2).unwrap();
+    assert_eq!(
+        plan.steps[0].activity,
+        Activity::Fight("chicken".parse().unwrap())
+    );
+    // 9 turns per fight.
+    assert_eq!(
+        plan.duration,
+        Duration::from_secs(18 * u64::from(plan.actions))
+    );
+
+    let mut weak = fighter();
+    weak.elemental_attributes.attack.water = 0;
+    assert_eq!(
+        planner.plan(&weak, Skill::Fighting, 2),
+        Err(LevelingError::NoActivity {
+            skill: Skill::Fighting,
+            level: 1
+        })
+    );
+}
+
+#[test]
+fn test_outgrown_activities() {
+    let world = common::load_world();
+    let mut planner = LevelingPlanner::new(&world);
+    let mut fisher = fighter();
+    fisher.skills.set(
+        Skill::Fishing,
+        SkillProgress {
+            level: 12,
+            max_xp: 1000,
+            ..Default::default()
+        },
+    );
+    assert!(planner.plan(&fisher, Skill::Fishing, 13).is_ok());
+
+    // The fishing spot gave no XP: nothing else is left.
+    let gudgeon = Activity::Gather("gudgeon_fishing_spot".parse().unwrap());
+    planner.record(gudgeon, 1, 0, 25);
+    assert_eq!(
+        planner.plan(&fisher, Skill::Fishing, 13),
+        Err(LevelingError::NoActivity {
+            skill: Skill::Fishing,
+            level: 12
+        })
+    );
+}
+
+#[test]
+fn test_unknown_xp() {
+    let world = common::load_world();
+    let planner = LevelingPlanner::new(&world);
+    let mut character = fighter();
+    character
+        .skills
+        .set(Skill::Mining, SkillProgress::default());
+    assert_eq!(
+        planner.plan(&character, Skill::Mining, 2),
+        Err(LevelingError::UnknownXp {
+            skill: Skill::Mining,
+            level: 0
+        })
+    );
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use artifacts_rs::{
+    api::schema::r#type::{
+        character::Position,
+        map::{MapContent, MapContentType},
+    },
+    movement::{travel_time, Navigator, Stop},
+};
+use std::time::Duration;
+
+#[test]
+fn test_travel_time() {
+    let from = Position::new(0, 0);
+    let to = Position::new(2, -3);
+    assert_eq!(travel_time(&from, &to, 0), Duration::from_secs(25));
+    assert_eq!(travel_time(&from, &to, 20), Duration::from_secs(20));
+    assert_eq!(travel_time(&from, &from, 0), Duration::ZERO);
+}
+
+#[test]
+fn test_cheapest_bank() {
+    let world = common::load_world();
+    let navigator = Navigator::with_speed(&world, 0);
+
+    let (bank, duration) = navigator
+        .cheapest(
+            &Position::new(0, 0),
+            world.tiles_of_type(MapContentType::Bank),
+        )
+        .unwrap();
+    assert_eq!(bank.position(), Position::new(4, 1));
+    assert_eq!(duration, Duration::from_secs(25));
+}
+
+#[test]
+fn test_plan_trip() {
+    let world = common::load_world();
+    let navigator = Navigator::with_speed(&world, 0);
+    let copper_rocks = MapContent::new(MapContentType::Resource, "copper_rocks".parse().unwrap());
+    let mining = MapContent::new(MapContentType::Workshop, "mining".parse().unwrap());
+
+    let trip = navigator
+        .plan_trip(
+            &Position::new(6, 6),
+            &[
+                Stop::Content(copper_rocks),
+                Stop::Content(mining),
+                Stop::Type(MapContentType::Bank),
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(
+        trip.destinations().collect::<Vec<_>>(),
+        vec![
+            Position::new(2, 0),
+            Position::new(1, 2),
+            Position::new(4, 1)
+        ]
+    );
+    assert_eq!(trip.total, Duration::from_secs((10 + 3 + 4) * 5));
+
+    let missing = MapContent::new(MapContentType::Monster, "dragon".parse().unwrap());
+    assert!(navigator
+        .plan_trip(&Position::new(0, 0), &[Stop::Content(missing)])
+        .is_none());
+}
@@ -1,8 +1,9 @@
 use artifacts_rs::api::schema::{
+    r#type::fight::{Combatant, Element, FightEvent},
     response::my_characters::{
-        CharacterCraftData, CharacterEquipData, CharacterFightData, CharacterGatherData,
-        CharacterGoldTransactionData, CharacterItemTransactionData, CharacterMovementData,
-        CharacterRecycleData, CharacterRestData, CharacterUseItemData,
+        CharacterBankExpansionData, CharacterCraftData, CharacterEquipData, CharacterFightData,
+        CharacterGatherData, CharacterGoldTransactionData, CharacterItemTransactionData,
+        CharacterMovementData, CharacterRecycleData, CharacterRestData, CharacterUseItemData,
     },
     SchemaWrapper,
 };
@@ -84,8 +85,58 @@ fn test_character_withdraw_bank_item() {
     assert!(serde_json::from_str::<SchemaWrapper<CharacterItemTransactionData>>(&data).is_ok());
 }
 
+#[test]
+fn test_character_buy_bank_expansion() {
+    let data = load_test_data("character_buy_bank_expansion.json");
+    assert!(serde_json::from_str::<SchemaWrapper<CharacterBankExpansionData>>(&data).is_ok());
+}
+
 #[test]
 fn test_character_recycle() {
     let data = load_test_data("character_recycle.json");
     assert!(serde_json::from_str::<SchemaWrapper<CharacterRecycleData>>(&data).is_ok());
 }
+
+/// The logs follow the format of the server fight logs, but this response
+/// was written by hand rather than captured; swap in a captured fight when
+/// one is available.
+#[test]
+fn test_character_fight_logs() {
+    let data = load_test_data("character_fight_logs.json");
+    let fight = serde_json::from_str::<SchemaWrapper<CharacterFightData>>(&data)
+        .unwrap()
+        .data
+        .fight;
+
+    let events = fight.events();
+    assert_eq!(
+        events[0],
+        FightEvent::Start {
+            character_hp: 120,
+            monster_hp: 80
+        }
+    );
+    assert_eq!(
+        events[6],
+        FightEvent::Attack {
+            turn: 5,
+            attacker: Combatant::Character,
+            element: Element::Water,
+            damage: 37,
+            critical: true
+        }
+    );
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, FightEvent::Unknown(_))));
+
+    let summary = fight.summary();
+    assert_eq!(summary.damage_dealt.water, 87);
+    assert_eq!(summary.damage_taken.fire, 18);
+    assert_eq!(summary.effect_damage_taken, 4);
+    assert_eq!(summary.healed, 16);
+    assert_eq!(summary.character_criticals, 1);
+    assert_eq!(summary.blocked_by_monster, fight.monster_blocked_hits.hits);
+    assert_eq!(summary.turns as i32, fight.turns);
+    assert_eq!(summary.result, Some(fight.result));
+}
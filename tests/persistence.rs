@@ -0,0 +1,204 @@
+mod common;
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use artifacts_rs::{
+    action::{CharacterAction, Craft, Deposit, Gather, Move, Rest},
+    api::schema::{
+        r#type::character::{Character, Position},
+        response::{my_characters::CharacterRestData, ResponseData, ResponseError},
+        SchemaWrapper,
+    },
+    fleet::{Fleet, RateLimit},
+    goal::Goal,
+    persistence::{CharacterSnapshot, Snapshot, Store},
+    runner::Executor,
+};
+
+/// Rests, each request taking ten seconds.
+struct Slow;
+
+impl Executor for Slow {
+    async fn execute(
+        &self,
+        name: &str,
+        _action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let data = fs::read_to_string("tests/data/my_characters/character_rest.json").unwrap();
+        let mut data = serde_json::from_str::<SchemaWrapper<CharacterRestData>>(&data)
+            .unwrap()
+            .data;
+        data.character.name = name.to_string();
+        Ok(data.into())
+    }
+}
+
+/// A character at (x, y) whose last cooldown ended at the start of 2024.
+fn located(name: &str, x: i32, y: i32) -> Character {
+    let mut character = common::character(name, &[]);
+    character.position = Position { x, y };
+    character.cooldown_info.cooldown_expiration = Some("2024-01-01T00:00:00Z".into());
+    character
+}
+
+fn temporary(file_name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", std::process::id(), file_name))
+}
+
+#[tokio::test]
+async fn test_store() {
+    let store = Store::new(temporary("state.json"));
+    assert!(store.load().unwrap().is_none());
+
+    let mut snapshot = Snapshot::default();
+    snapshot.characters.insert(
+        "alice".into(),
+        CharacterSnapshot {
+            queue: vec![Gather.into()],
+            in_flight: Some(Move::new(2, 0).into()),
+            cooldown_expiration: Some("2024-01-01T00:00:00Z".into()),
+            goal: Some(Goal::BankItem {
+                code: "copper".parse().unwrap(),
+                quantity: 20,
+            }),
+        },
+    );
+    snapshot.bank = Some(common::bank(120, &[("copper", 10)]));
+    snapshot.count("fights", 3);
+    snapshot.count("fights", 2);
+    store.save(&snapshot).unwrap();
+
+    let loaded = store.load().unwrap().unwrap();
+    assert_eq!(loaded.characters, snapshot.characters);
+    assert_eq!(loaded.counter("fights"), 5);
+    let loaded_bank = loaded.bank.unwrap();
+    assert_eq!(loaded_bank.quantity("copper"), 10);
+    assert_eq!(loaded_bank.gold(), 120);
+    assert_eq!(loaded_bank.slots(), 50);
+    // A loaded mirror is stale until reconciled.
+    let every = Duration::from_secs(3600);
+    assert!(!common::bank(120, &[("copper", 10)]).needs_sync(every));
+    assert!(loaded_bank.needs_sync(every));
+
+    // Saving again replaces the file, leaving no temporary file behind.
+    store.save(&Snapshot::default()).unwrap();
+    assert!(store.load().unwrap().unwrap().characters.is_empty());
+    let mut leftover = store.path().as_os_str().to_owned();
+    leftover.push(".tmp");
+    assert!(!PathBuf::from(leftover).exists());
+
+    fs::write(store.path(), "{").unwrap();
+    assert!(store.load().is_err());
+    fs::remove_file(store.path()).unwrap();
+}
+
+#[test]
+fn test_queue_reconciliation() {
+    let mut snapshot = Snapshot::default();
+    snapshot.characters.insert(
+        "alice".into(),
+        CharacterSnapshot {
+            queue: vec![Gather.into()],
+            in_flight: Some(Move::new(2, 0).into()),
+            ..Default::default()
+        },
+    );
+
+    // The move was done before the crash.
+    assert_eq!(
+        snapshot.queue(&located("alice", 2, 0)).unwrap(),
+        [CharacterAction::from(Gather)]
+    );
+    // The move was not sent, or failed.
+    assert_eq!(
+        snapshot.queue(&located("alice", 0, 0)).unwrap(),
+        [CharacterAction::from(Move::new(2, 0)), Gather.into()]
+    );
+    assert_eq!(snapshot.queue(&located("bob", 2, 0)), Some(Vec::new()));
+
+    // Nothing was in flight: the queue is kept as it is.
+    snapshot.characters.get_mut("alice").unwrap().in_flight = None;
+    assert_eq!(snapshot.queue(&located("alice", 0, 0)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_crash_after_craft() {
+    let copper = "copper".parse().unwrap();
+    let mut snapshot = Snapshot::default();
+    snapshot.characters.insert(
+        "alice".into(),
+        CharacterSnapshot {
+            queue: vec![Move::new(4, 1).into(), Deposit::new(copper, 1).into()],
+            in_flight: Some(Craft::new("copper".parse().unwrap(), 1).into()),
+            cooldown_expiration: Some("2024-01-01T00:00:00Z".into()),
+            goal: None,
+        },
+    );
+
+    // The craft completed: its cooldown replaced the one saved.
+    let mut crafted = located("alice", 1, 2);
+    crafted.cooldown_info.cooldown_expiration = Some("2024-01-01T00:00:25Z".into());
+    let queue = snapshot.queue(&crafted).unwrap();
+    assert_eq!(queue.len(), 2);
+    assert!(matches!(queue[0], CharacterAction::Move(_)));
+
+    // The server never saw it: it is sent again.
+    let queue = snapshot.queue(&located("alice", 1, 2)).unwrap();
+    assert_eq!(queue.len(), 3);
+    assert!(matches!(queue[0], CharacterAction::Craft(_)));
+
+    // Without a cooldown to compare, the goal has to be planned again.
+    snapshot
+        .characters
+        .get_mut("alice")
+        .unwrap()
+        .cooldown_expiration = None;
+    assert_eq!(snapshot.queue(&crafted), None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_capture() {
+    let fleet = Fleet::new(
+        Slow,
+        RateLimit::new(10, Duration::from_secs(1)),
+        vec![located("alice", 0, 0), located("bob", 0, 0)],
+        common::bank(120, &[("copper", 10)]),
+    );
+    let mut snapshot = Snapshot::default();
+    snapshot.set_goal(
+        "alice",
+        Some(Goal::Equip {
+            code: "copper_dagger".parse().unwrap(),
+        }),
+    );
+    fleet
+        .handle("alice")
+        .unwrap()
+        .extend([Rest.into(), Move::new(1, 1).into()]);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // The rest is waiting for its response, with the cooldown it started from.
+    snapshot.capture(&fleet);
+    let alice = &snapshot.characters["alice"];
+    assert_eq!(alice.in_flight, Some(Rest.into()));
+    assert_eq!(alice.queue, [CharacterAction::from(Move::new(1, 1))]);
+    assert_eq!(
+        alice.cooldown_expiration.as_deref(),
+        Some("2024-01-01T00:00:00Z")
+    );
+    assert!(alice.goal.is_some());
+    assert!(snapshot.characters["bob"].queue.is_empty());
+    assert_eq!(snapshot.bank.as_ref().unwrap().quantity("copper"), 10);
+
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    snapshot.capture(&fleet);
+    let alice = &snapshot.characters["alice"];
+    assert_eq!(alice.in_flight, Some(Move::new(1, 1).into()));
+    assert!(alice.queue.is_empty());
+    assert_eq!(
+        alice.cooldown_expiration.as_deref(),
+        Some("2019-08-24T14:15:22Z")
+    );
+    fleet.shutdown().await;
+}
@@ -0,0 +1,185 @@
+use std::{fs, sync::Arc, time::Duration};
+
+use artifacts_rs::{
+    action::{ActionOutcome, CharacterAction, Fight, Gather, Move, Rest},
+    api::schema::{
+        r#type::time::ServerClock,
+        response::{
+            error_code,
+            my_characters::{CharacterFightData, CharacterMovementData, CharacterRestData},
+            ResponseData, ResponseError,
+        },
+        SchemaWrapper,
+    },
+    runner::{remaining_cooldown, CharacterRunner, Executor, RunState},
+};
+use serde::de::DeserializeOwned;
+use tokio::time::Instant;
+
+fn load<T: DeserializeOwned>(path: &str) -> T {
+    let data =
+        fs::read_to_string(format!("tests/data/{path}")).expect("Failed to read test data file");
+    serde_json::from_str::<SchemaWrapper<T>>(&data)
+        .expect("Failed to deserialize test data")
+        .data
+}
+
+/// Answers with recorded responses; gathering always fails.
+struct Recorded;
+
+impl Executor for Recorded {
+    async fn execute(
+        &self,
+        _name: &str,
+        action: &CharacterAction,
+    ) -> Result<ResponseData, ResponseError> {
+        match action {
+            CharacterAction::Move(_) => {
+                Ok(load::<CharacterMovementData>("my_characters/character_movement.json").into())
+            }
+            CharacterAction::Rest(_) => {
+                Ok(load::<CharacterRestData>("my_characters/character_rest.json").into())
+            }
+            CharacterAction::Fight(_) => {
+                Ok(load::<CharacterFightData>("simulated_fights/chicken.json").into())
+            }
+            _ => Err(ResponseError::new(
+                error_code::INVENTORY_FULL,
+                "Character inventory is full.".into(),
+            )),
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runner_cooldowns() {
+    let (runner, handle, mut results) = CharacterRunner::new("fighter", Arc::new(Recorded));
+    assert_eq!(runner.name(), "fighter");
+    handle.push(Fight);
+    handle.push(Move::new(0, 1));
+    let task = tokio::spawn(runner.run());
+
+    let start = Instant::now();
+    let fight = results.recv().await.unwrap();
+    assert_eq!(fight.name, "fighter");
+    assert_eq!(fight.action, Fight.into());
+    assert_eq!(fight.result.unwrap().cooldown().remaining_seconds, 18);
+    assert_eq!(handle.character().unwrap().name, "fighter");
+
+    // The move waits for the fight cooldown.
+    let movement = results.recv().await.unwrap();
+    assert!(matches!(
+        movement.result,
+        Ok(ResponseData::CharacterMovement(_))
+    ));
+    assert!(start.elapsed() >= Duration::from_secs(18));
+    assert_eq!(handle.character().unwrap().name, "string");
+
+    // An idle runner picks up new actions.
+    handle.push(Rest);
+    assert!(results.recv().await.unwrap().result.is_ok());
+
+    handle.cancel();
+    task.await.unwrap();
+    assert_eq!(handle.state(), RunState::Cancelled);
+    handle.resume();
+    assert_eq!(handle.state(), RunState::Cancelled);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runner_pauses_on_error() {
+    let (runner, handle, mut results) = CharacterRunner::new("fighter", Arc::new(Recorded));
+    handle.extend([Gather.into(), Move::new(0, 1).into()]);
+    let task = tokio::spawn(runner.run());
+
+    let gather = results.recv().await.unwrap();
+    assert_eq!(
+        gather.result.unwrap_err().error.code,
+        error_code::INVENTORY_FULL
+    );
+    // The failed action waits at the front of the queue.
+    assert_eq!(handle.state(), RunState::Paused);
+    assert_eq!(
+        handle.pending(),
+        [CharacterAction::from(Gather), Move::new(0, 1).into()]
+    );
+    assert!(handle.character().is_none());
+
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    assert!(results.try_recv().is_err());
+
+    handle.clear();
+    handle.push(Rest);
+    handle.resume();
+    let rest = results.recv().await.unwrap();
+    assert_eq!(rest.action, Rest.into());
+    assert!(handle.pending().is_empty());
+
+    handle.cancel();
+    task.await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runner_cancel_during_cooldown() {
+    let (runner, handle, mut results) = CharacterRunner::new("fighter", Arc::new(Recorded));
+    handle.extend([Fight.into(), Move::new(0, 1).into()]);
+    let task = tokio::spawn(runner.run());
+
+    results.recv().await.unwrap();
+    handle.cancel();
+    task.await.unwrap();
+    assert_eq!(handle.pending().len(), 1);
+    assert!(results.recv().await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runner_pause() {
+    let (runner, handle, mut results) = CharacterRunner::new("fighter", Arc::new(Recorded));
+    handle.pause();
+    handle.push(Rest);
+    let task = tokio::spawn(runner.run());
+
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    assert!(results.try_recv().is_err());
+    assert_eq!(handle.pending().len(), 1);
+
+    handle.resume();
+    assert!(results.recv().await.unwrap().result.is_ok());
+    handle.cancel();
+    task.await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runner_initial_cooldown() {
+    let (runner, handle, mut results) = CharacterRunner::new("fighter", Arc::new(Recorded));
+    handle.push(Rest);
+    let task = tokio::spawn(runner.with_cooldown(Duration::from_secs(30)).run());
+
+    let start = Instant::now();
+    results.recv().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(30));
+
+    handle.cancel();
+    task.await.unwrap();
+}
+
+#[test]
+fn test_remaining_cooldown() {
+    let clock = ServerClock::default();
+    let mut character = load::<CharacterRestData>("my_characters/character_rest.json").character;
+    // The fixture's cooldown expired long ago.
+    assert_eq!(remaining_cooldown(&character, &clock), Duration::ZERO);
+
+    character.cooldown_info.cooldown = 25;
+    character.cooldown_info.cooldown_expiration = Some("2999-01-01T00:00:00Z".into());
+    #[cfg(feature = "chrono")]
+    assert!(remaining_cooldown(&character, &clock) > Duration::from_secs(3600));
+    // The expiration cannot be read, the whole cooldown is waited for.
+    #[cfg(not(feature = "chrono"))]
+    assert_eq!(
+        remaining_cooldown(&character, &clock),
+        Duration::from_secs(25)
+    );
+    character.cooldown_info.cooldown_expiration = None;
+    assert_eq!(remaining_cooldown(&character, &clock), Duration::ZERO);
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use artifacts_rs::{
+    api::schema::r#type::{
+        character::{Character, Position, Skill, SkillProgress},
+        item::{Craft, Item, ItemCode},
+    },
+    sourcing::{Source, Sourcing},
+    world::World,
+};
+
+fn code(code: &str) -> ItemCode {
+    code.parse().unwrap()
+}
+
+fn gatherer(mining: u32, alchemy: u32) -> Character {
+    let mut character = common::character("", &[]);
+    for (skill, level) in [(Skill::Mining, mining), (Skill::Alchemy, alchemy)] {
+        character.skills.set(
+            skill,
+            SkillProgress {
+                level,
+                ..Default::default()
+            },
+        );
+    }
+    character
+}
+
+#[test]
+fn test_sources() {
+    let world = common::load_world();
+    let sourcing = Sourcing::new(&world);
+
+    match sourcing.sources(&code("copper_ore")) {
+        [Source::Resource {
+            resource,
+            skill,
+            tiles,
+            ..
+        }] => {
+            assert_eq!(resource.as_str(), "copper_rocks");
+            assert_eq!(*skill, Skill::Mining);
+            assert_eq!(tiles, &[Position::new(2, 0)]);
+        }
+        sources => panic!("unexpected sources {sources:?}"),
+    }
+    assert!(matches!(
+        sourcing.sources(&code("feather")),
+        [Source::Monster { .. }]
+    ));
+    assert!(matches!(
+        sourcing.sources(&code("copper")),
+        [Source::Craft { .. }]
+    ));
+    assert!(sourcing.sources(&code("wooden_stick")).is_empty());
+}
+
+#[test]
+fn test_rank_sources() {
+    let world = common::load_world();
+    let sourcing = Sourcing::new(&world);
+
+    // 10 ores at one gather each, plus the craft itself.
+    let copper = sourcing.rank(&code("copper"), &gatherer(1, 1));
+    assert_eq!(copper.len(), 1);
+    assert!(copper[0].available);
+    assert_eq!(copper[0].actions_per_unit, 11.0);
+
+    let potion = sourcing.rank(&code("small_health_potion"), &gatherer(1, 1));
+    assert!(!potion[0].available);
+    assert_eq!(
+        sourcing.best_actions_per_unit(&code("small_health_potion"), &gatherer(1, 5)),
+        1.0 + 10.0 + 12.0
+    );
+    assert!(sourcing
+        .best_actions_per_unit(&code("small_health_potion"), &gatherer(1, 1))
+        .is_infinite());
+}
+
+#[test]
+fn test_cycle_not_cached() {
+    // Copper ore can also be made from copper, which is made from copper ore,
+    // and a copper dagger takes one of each.
+    let mut items: Vec<Item> = common::load_paged("items.json");
+    for item in &mut items {
+        let materials = match item.code.as_str() {
+            "copper_ore" => common::items(&[("copper", 1)]),
+            "copper_dagger" => common::items(&[("copper_ore", 1), ("copper", 1)]),
+            _ => continue,
+        };
+        item.craft = Some(Craft {
+            skill: Skill::Mining,
+            level: 1,
+            items: materials,
+            quantity: 1,
+        });
+    }
+    let world = World::new(
+        common::load_paged("maps.json"),
+        common::load_paged("monsters.json"),
+        common::load_paged("resources.json"),
+        items,
+    );
+    let sourcing = Sourcing::new(&world);
+
+    // Copper was first reached through its ore, where the cycle was cut.
+    assert_eq!(
+        sourcing.best_actions_per_unit(&code("copper_dagger"), &gatherer(1, 1)),
+        1.0 + 1.0 + 11.0
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use artifacts_rs::statistics::{expected_gold, DropRate};
+
+fn drop(rate: u32, min_quantity: u32, max_quantity: u32) -> DropRate {
+    DropRate {
+        rate,
+        min_quantity,
+        max_quantity,
+    }
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{a} != {b}");
+}
+
+#[test]
+fn test_expected_yield() {
+    let egg = drop(12, 1, 1);
+    assert_close(egg.expected_per_action(), 1.0 / 12.0);
+    assert_close(egg.expected_after(24), 2.0);
+    assert_close(egg.expected_actions_for(3), 36.0);
+
+    let ore = drop(1, 1, 3);
+    assert_close(ore.expected_per_action(), 2.0);
+}
+
+#[test]
+fn test_probability_at_least() {
+    let coin = drop(2, 1, 1);
+    assert_close(coin.probability_at_least(1, 1), 0.5);
+    assert_close(coin.probability_at_least(1, 2), 0.75);
+    assert_close(coin.probability_at_least(2, 2), 0.25);
+    assert_close(coin.probability_at_least(0, 0), 1.0);
+
+    let ore = drop(1, 1, 2);
+    assert_close(ore.probability_at_least(3, 2), 0.75);
+}
+
+#[test]
+fn test_actions_for_confidence() {
+    let coin = drop(2, 1, 1);
+    assert_eq!(coin.actions_for(1, 0.5), Some(1));
+    assert_eq!(coin.actions_for(1, 0.9), Some(4));
+    assert_eq!(coin.actions_for(0, 0.99), Some(0));
+    assert_eq!(drop(1, 0, 0).actions_for(1, 0.5), None);
+
+    let feather = drop(8, 1, 1);
+    let actions = feather.actions_for(5, 0.95).unwrap();
+    assert!(feather.probability_at_least(5, actions) >= 0.95);
+    assert!(feather.probability_at_least(5, actions - 1) < 0.95);
+}
+
+#[test]
+fn test_actions_for_certainty() {
+    // Rounding keeps these from ever being certain.
+    for (drop, quantity) in [(drop(100, 1, 5), 50), (drop(7, 2, 9), 33)] {
+        let actions = drop.actions_for(quantity, 1.0).unwrap();
+        assert!(actions >= drop.actions_for(quantity, 0.999999).unwrap());
+        assert!(drop.probability_at_least(quantity, actions) > 0.999999);
+    }
+}
+
+#[test]
+fn test_expected_gold() {
+    let world = common::load_world();
+    let chicken = world.monster(&"chicken".parse().unwrap()).unwrap();
+    assert_close(expected_gold(chicken), 1.5);
+}
@@ -0,0 +1,80 @@
+use artifacts_rs::api::schema::{
+    r#type::time::Timestamp, response::my_characters::CharacterMovementData, SchemaWrapper,
+};
+use std::fs;
+
+fn load_movement() -> CharacterMovementData {
+    let data = fs::read_to_string("tests/data/my_characters/character_movement.json").unwrap();
+    serde_json::from_str::<SchemaWrapper<CharacterMovementData>>(&data)
+        .unwrap()
+        .data
+}
+
+#[test]
+fn test_timestamps() {
+    // The field types must not depend on the `chrono` feature.
+    let cooldown = load_movement().cooldown;
+    let expiration: &Timestamp = &cooldown.expiration;
+    assert_eq!(expiration, "2019-08-24T14:15:22Z");
+    assert_eq!(cooldown.started_at.as_str(), "2019-08-24T14:15:22Z");
+    assert_eq!(
+        serde_json::to_value(expiration).unwrap(),
+        "2019-08-24T14:15:22Z"
+    );
+}
+
+#[cfg(not(feature = "chrono"))]
+#[test]
+fn test_unreadable_without_chrono() {
+    use artifacts_rs::api::schema::r#type::time::ServerClock;
+
+    let expiration = load_movement().cooldown.expiration;
+    assert_eq!(ServerClock::default().until(&expiration), None);
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_feature {
+    use super::load_movement;
+    use artifacts_rs::api::schema::r#type::time::{ServerClock, Timestamp};
+    use chrono::{DateTime, TimeDelta, Utc};
+    use std::time::Duration;
+
+    fn timestamp(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cooldown_remaining() {
+        let cooldown = load_movement().cooldown;
+
+        assert_eq!(
+            cooldown.expiration.to_datetime(),
+            Some(timestamp("2019-08-24T14:15:22Z"))
+        );
+        assert_eq!(Timestamp::from("not a date").to_datetime(), None);
+        assert_eq!(
+            cooldown.remaining(timestamp("2019-08-24T14:15:12Z")),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            cooldown.remaining(timestamp("2019-08-24T14:16:00Z")),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_server_clock_skew() {
+        let clock = ServerClock::new(
+            timestamp("2024-01-01T00:00:05Z"),
+            timestamp("2024-01-01T00:00:00Z"),
+        );
+        assert_eq!(clock.skew(), TimeDelta::seconds(5));
+
+        // A server an hour ahead sees an expiration in 30 minutes as passed.
+        let ahead = ServerClock::new(Utc::now() + TimeDelta::hours(1), Utc::now());
+        let expiration = Timestamp::new((Utc::now() + TimeDelta::minutes(30)).to_rfc3339());
+        assert_eq!(ahead.until(&expiration), Some(Duration::ZERO));
+        let remaining = ServerClock::default().until(&expiration).unwrap();
+        assert!(remaining > Duration::from_secs(29 * 60));
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use artifacts_rs::api::schema::r#type::{
+    character::Position,
+    map::{Map, MapContent, MapContentType},
+};
+
+fn content(r#type: MapContentType, code: &str) -> MapContent {
+    MapContent {
+        r#type,
+        code: code.parse().unwrap(),
+    }
+}
+
+#[test]
+fn test_world_lookup() {
+    let world = common::load_world();
+
+    let tile = world.tile(&Position::new(2, 0)).unwrap();
+    assert_eq!(
+        tile.content,
+        Some(content(MapContentType::Resource, "copper_rocks"))
+    );
+    assert_eq!(world.tiles_of_type(MapContentType::Bank).count(), 2);
+    assert!(world.item(&"copper_dagger".parse().unwrap()).is_some());
+    assert!(world.monster(&"chicken".parse().unwrap()).is_some());
+}
+
+#[test]
+fn test_world_nearest() {
+    let world = common::load_world();
+
+    let bank = world
+        .nearest_of_type(&Position::new(6, 6), MapContentType::Bank)
+        .unwrap();
+    assert_eq!(bank.position(), Position::new(7, 5));
+
+    let workshop = world
+        .nearest(
+            &Position::new(0, 0),
+            &content(MapContentType::Workshop, "weaponcrafting"),
+        )
+        .unwrap();
+    assert_eq!(workshop.position(), Position::new(1, 5));
+}
+
+#[test]
+fn test_world_update_tile() {
+    let mut world = common::load_world();
+    let event = content(MapContentType::Monster, "yellow_slime");
+
+    world.update_tile(Map {
+        name: "Spawn".to_string(),
+        skin: "spawn".to_string(),
+        x: 0,
+        y: 0,
+        content: Some(event.clone()),
+    });
+    assert_eq!(world.tiles_with(&event).count(), 2);
+    assert_eq!(
+        world
+            .nearest(&Position::new(0, 0), &event)
+            .unwrap()
+            .position(),
+        Position::new(0, 0)
+    );
+
+    world.update_tile(Map {
+        name: "Spawn".to_string(),
+        skin: "spawn".to_string(),
+        x: 0,
+        y: 0,
+        content: None,
+    });
+    assert_eq!(world.tiles_with(&event).count(), 1);
+}